@@ -0,0 +1,30 @@
+//! Print levels of the index ordered by difficulty. It explores every state
+//! of each level, so build it in release mode:
+//!
+//! `cargo run -p cube-collection --release --example difficulty`
+
+mod levels;
+
+use cube_core::difficulty::{estimate, Difficulty};
+
+// states explored per level at most, and levels with more are unknown.
+// a million states take minutes and gigabytes of memory on some levels.
+const LIMIT: usize = 100_000;
+
+fn main() {
+    let index = levels::index();
+    let mut rates = Vec::with_capacity(index.name_list.len());
+    for name in index.name_list.iter() {
+        let seed = levels::level(&index, name);
+        rates.push((name, estimate(&seed, LIMIT)));
+    }
+
+    let score = |rate: &Option<Difficulty>| rate.as_ref().map(|o| o.score);
+    rates.sort_by(|l, r| score(&l.1).partial_cmp(&score(&r.1)).unwrap());
+    for (name, rate) in rates {
+        match rate {
+            Some(rate) => println!("{}: {}", name, rate),
+            None => println!("{}: unknown", name),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Print the state graph of a small level in DOT, e.g.
+//!
+//! `cargo run -p cube-collection --release --example graph -- swap > swap.dot`
+
+mod levels;
+
+use cube_core::explore::StateGraph;
+
+// states explored at most, as the graph is only readable for small levels.
+const LIMIT: usize = 100_000;
+
+fn main() {
+    let name = std::env::args().nth(1).expect("no level name");
+    let seed = levels::level(&levels::index(), &name);
+    let graph = StateGraph::explore(&seed, LIMIT).expect("too many states");
+    print!("{}", graph.to_dot());
+    eprintln!("{} states of {}", graph.states(), name);
+}
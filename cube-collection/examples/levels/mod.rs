@@ -0,0 +1,51 @@
+//! Levels shipped in `assets`, read without Bevy for the examples.
+
+// each example uses a part of this module.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use cube_core::{cube::Movement, seed::Seed};
+use serde::Deserialize;
+
+#[path = "../../src/plugin/loader_plugin/level.rs"]
+mod level;
+
+#[derive(Deserialize)]
+pub struct LevelIndex {
+    pub directory: String,
+    pub extension: String,
+    pub name_list: Vec<String>,
+}
+
+pub fn index() -> LevelIndex {
+    let texts = std::fs::read_to_string(root().join("level/index.toml")).unwrap();
+    toml::from_str::<LevelIndex>(&texts).unwrap()
+}
+
+pub fn level(index: &LevelIndex, name: &str) -> Seed {
+    let path = root()
+        .join(&index.directory)
+        .join([name, ".", &index.extension].concat());
+    let texts =
+        std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("no level at {}", path.display()));
+    let source = toml::from_str::<level::LevelSource>(&texts).unwrap();
+    source.into_seed().unwrap()
+}
+
+/// Read inputs of `L`, `D`, `U`, `R` and `I` for idle.
+pub fn inputs_of(text: &str) -> Vec<Option<Movement>> {
+    text.chars()
+        .map(|c| match c {
+            'L' => Some(Movement::Left),
+            'D' => Some(Movement::Down),
+            'U' => Some(Movement::Up),
+            'R' => Some(Movement::Right),
+            _ => None,
+        })
+        .collect()
+}
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+}
@@ -0,0 +1,22 @@
+//! Shrink a level while units overlap when playing the inputs, which are a
+//! string of `L`, `D`, `U`, `R` and `I` for idle, e.g.
+//!
+//! `cargo run -p cube-collection --example minimize -- debug-slap RRDI`
+//!
+//! It prints the smallest level found as TOML.
+
+mod levels;
+
+use cube_core::minimize::{minimize, overlapped};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let name = args.next().expect("no level name");
+    let inputs = levels::inputs_of(&args.next().expect("no inputs"));
+
+    let seed = levels::level(&levels::index(), &name);
+    assert!(overlapped(&seed, &inputs), "no overlap in {}", name);
+    let (seed, inputs) = minimize(&seed, &inputs, overlapped);
+    println!("# inputs: {:?}", inputs);
+    println!("{}", seed.to_toml().unwrap());
+}
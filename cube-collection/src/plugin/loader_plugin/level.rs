@@ -25,6 +25,36 @@ pub enum LevelError {
 
     #[snafu(display("expect a valid location, but get ({}, {})", position.0, position.1))]
     InvalidLocation { position: (i32, i32) },
+
+    #[snafu(display("expect mirror string, but get '{}'", name))]
+    InvalidMirror { name: String },
+
+    #[snafu(display("expect pursuit string, but get '{}'", name))]
+    InvalidPursuit { name: String },
+
+    #[snafu(display("expect gravity string, but get '{}'", name))]
+    InvalidGravity { name: String },
+
+    #[snafu(display("expect conveyor string, but get '{}'", name))]
+    InvalidConveyor { name: String },
+
+    #[snafu(display("expect rotator string, but get '{}'", name))]
+    InvalidRotator { name: String },
+
+    #[snafu(display("expect cutter string, but get '{}'", name))]
+    InvalidCutter { name: String },
+
+    #[snafu(display("expect falling string, but get '{}'", name))]
+    InvalidFalling { name: String },
+
+    #[snafu(display("expect key string, but get '{}'", name))]
+    InvalidKey { name: String },
+
+    #[snafu(display("expect lock string, but get '{}'", name))]
+    InvalidLock { name: String },
+
+    #[snafu(display("expect positive blinker period, but get {}", period))]
+    InvalidPeriod { period: u32 },
 }
 
 #[derive(Deserialize)]
@@ -43,6 +73,16 @@ struct Info {
 struct Map {
     raw: String,
     commands: Option<Vec<Command>>,
+    mirrors: Option<Vec<Mirror>>,
+    pursuits: Option<Vec<Pursuit>>,
+    conveyors: Option<Vec<Conveyor>>,
+    rotators: Option<Vec<Rotator>>,
+    cutters: Option<Vec<Cutter>>,
+    keys: Option<Vec<Key>>,
+    locks: Option<Vec<Lock>>,
+    blinkers: Option<Vec<Blinker>>,
+    gravity: Option<String>,
+    falling: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -52,6 +92,55 @@ struct Command {
     binding: Vec<[i32; 2]>,
 }
 
+#[derive(Deserialize)]
+struct Mirror {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Pursuit {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Conveyor {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Rotator {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Cutter {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Key {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Lock {
+    content: String,
+    binding: Vec<[i32; 2]>,
+}
+
+#[derive(Deserialize)]
+struct Blinker {
+    period: u32,
+    phase: Option<u32>,
+    binding: Vec<[i32; 2]>,
+}
+
 impl LevelSource {
     pub fn into_seed(self) -> Result<seed::Seed, LevelError> {
         ensure!(
@@ -76,6 +165,13 @@ impl LevelSource {
                     'R' | 'r' => parser.make_cube(cube::Kind::Red),
                     'B' | 'b' => parser.make_cube(cube::Kind::Blue),
                     'G' | 'g' => parser.make_cube(cube::Kind::Green),
+                    'C' | 'c' => parser.make_cube(cube::Kind::Crate),
+                    'K' | 'k' => parser.make_cube(cube::Kind::Brick),
+                    'O' | 'o' => parser.make_cube(cube::Kind::Bomb),
+                    '%' => parser.make_paint(cube::Kind::Red),
+                    '$' => parser.make_paint(cube::Kind::Blue),
+                    '&' => parser.make_paint(cube::Kind::Green),
+                    '.' => parser.make_pit(),
                     'X' | 'x' => parser.make_destination(),
                     ' ' => parser.make_empty(),
                     '-' => parser.copy_left()?,
@@ -112,6 +208,118 @@ impl LevelSource {
                 parser.bind_command(p[0], p[1], c.clone())?;
             }
         }
+        for m in self.map.mirrors.unwrap_or_default() {
+            let mirror = match m.content.as_str() {
+                "opposite" => cube::Mirror::Opposite,
+                "horizontal" => cube::Mirror::Horizontal,
+                "vertical" => cube::Mirror::Vertical,
+                "clockwise" => cube::Mirror::Clockwise,
+                "anticlockwise" => cube::Mirror::Anticlockwise,
+                _ => return InvalidMirror { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.bind_mirror(p[0], p[1], mirror)?;
+            }
+        }
+        for m in self.map.pursuits.unwrap_or_default() {
+            let pursuit = match m.content.as_str() {
+                "chase" => cube::Pursuit::Chase,
+                "flee" => cube::Pursuit::Flee,
+                _ => return InvalidPursuit { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.bind_pursuit(p[0], p[1], pursuit)?;
+            }
+        }
+        for m in self.map.conveyors.unwrap_or_default() {
+            let tile = match direction(&m.content) {
+                Some(movement) => cube::Tile::Conveyor(movement),
+                None => return InvalidConveyor { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.put_tile(p[0], p[1], tile)?;
+            }
+        }
+        for m in self.map.rotators.unwrap_or_default() {
+            let clockwise = match m.content.as_str() {
+                "clockwise" => true,
+                "anticlockwise" => false,
+                _ => return InvalidRotator { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.put_tile(p[0], p[1], cube::Tile::Rotator { clockwise })?;
+            }
+        }
+        for m in self.map.cutters.unwrap_or_default() {
+            let tile = match direction(&m.content) {
+                Some(side) => cube::Tile::Cutter(side),
+                None => return InvalidCutter { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.put_tile(p[0], p[1], tile)?;
+            }
+        }
+        for m in self.map.keys.unwrap_or_default() {
+            let tile = match color(&m.content) {
+                Some(kind) => cube::Tile::Key(kind),
+                None => return InvalidKey { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.put_tile(p[0], p[1], tile)?;
+            }
+        }
+        for m in self.map.locks.unwrap_or_default() {
+            let tile = match color(&m.content) {
+                Some(kind) => cube::Tile::Lock(kind),
+                None => return InvalidLock { name: m.content }.fail(),
+            };
+            for p in m.binding {
+                parser.put_tile(p[0], p[1], tile)?;
+            }
+        }
+        for m in self.map.blinkers.unwrap_or_default() {
+            ensure!(m.period > 0, InvalidPeriod { period: m.period });
+            let tile = cube::Tile::Blinker(cube::Blink {
+                period: m.period,
+                phase: m.phase.unwrap_or_default(),
+            });
+            for p in m.binding {
+                parser.put_tile(p[0], p[1], tile)?;
+            }
+        }
+        if let Some(name) = self.map.gravity {
+            match direction(&name) {
+                Some(movement) => parser.g = Some(movement),
+                None => return InvalidGravity { name }.fail(),
+            }
+        }
+        if let Some(name) = self.map.falling {
+            parser.f = match name.as_str() {
+                "whole" => cube::Falling::Whole,
+                "apart" => cube::Falling::Apart,
+                _ => return InvalidFalling { name }.fail(),
+            };
+        }
+
+        fn color(name: &str) -> Option<cube::Kind> {
+            match name {
+                "red" => Some(cube::Kind::Red),
+                "green" => Some(cube::Kind::Green),
+                "blue" => Some(cube::Kind::Blue),
+                _ => None,
+            }
+        }
+
+        fn direction(name: &str) -> Option<cube::Movement> {
+            match name {
+                "left" => Some(cube::Movement::Left),
+                "down" => Some(cube::Movement::Down),
+                "up" => Some(cube::Movement::Up),
+                "right" => Some(cube::Movement::Right),
+                _ => None,
+            }
+        }
+
         fn put<'a>(parser: &'a mut CommandParser, buffer: &mut String) -> &'a mut CommandParser {
             if !buffer.is_empty() {
                 if let Ok(i) = buffer.parse::<i32>() {
@@ -136,6 +344,9 @@ struct LevelParser {
     w: i32,
     cs: Vec<seed::Cube>,
     ds: Vec<cube::Point>,
+    ts: Vec<(cube::Point, cube::Tile)>,
+    g: Option<cube::Movement>,
+    f: cube::Falling,
 
     // cached
     x: i32,
@@ -160,6 +371,9 @@ impl Into<seed::Seed> for LevelParser {
             },
             cubes: self.cs,
             destnations: self.ds,
+            tiles: self.ts,
+            gravity: self.g,
+            falling: self.f,
         }
     }
 }
@@ -173,6 +387,9 @@ impl LevelParser {
             w: 0,
             cs: Vec::new(),
             ds: Vec::new(),
+            ts: Vec::new(),
+            g: None,
+            f: cube::Falling::Whole,
             x: 0,
             m: LevelMapBuilder(vec![Vec::new()]),
         }
@@ -199,12 +416,26 @@ impl LevelParser {
         self.make(None);
     }
 
+    fn make_paint(&mut self, kind: cube::Kind) {
+        let tile = cube::Tile::Paint(kind);
+        self.ts.push((cube::Point::new(self.x, self.h), tile));
+        self.make(None);
+    }
+
+    fn make_pit(&mut self) {
+        self.ts
+            .push((cube::Point::new(self.x, self.h), cube::Tile::Pit));
+        self.make(None);
+    }
+
     fn make_cube(&mut self, kind: cube::Kind) {
         let i = self.cs.len();
         let c = seed::Cube {
             kind,
             body: vec![cube::Point::new(self.x, self.h)],
             command: None,
+            mirror: None,
+            pursuit: None,
         };
 
         self.cs.push(c);
@@ -301,6 +532,28 @@ impl LevelParser {
             None => Err(LevelError::InvalidLocation { position: (x, y) }),
         }
     }
+
+    fn bind_mirror(&mut self, x: i32, y: i32, mirror: cube::Mirror) -> Result<(), LevelError> {
+        match self.m.get(x, y).and_then(|i| self.cs.get_mut(i)) {
+            Some(x) => Ok(x.mirror = Some(mirror)),
+            None => Err(LevelError::InvalidLocation { position: (x, y) }),
+        }
+    }
+
+    fn bind_pursuit(&mut self, x: i32, y: i32, pursuit: cube::Pursuit) -> Result<(), LevelError> {
+        match self.m.get(x, y).and_then(|i| self.cs.get_mut(i)) {
+            Some(x) => Ok(x.pursuit = Some(pursuit)),
+            None => Err(LevelError::InvalidLocation { position: (x, y) }),
+        }
+    }
+
+    fn put_tile(&mut self, x: i32, y: i32, tile: cube::Tile) -> Result<(), LevelError> {
+        ensure!(
+            0 <= x && x < self.w && 0 <= y && y < self.h,
+            InvalidLocation { position: (x, y) }
+        );
+        Ok(self.ts.push((cube::Point::new(x, y), tile)))
+    }
 }
 
 struct LevelMapBuilder(
@@ -1,9 +1,10 @@
 use anyhow::Ok;
 use bevy::{
     asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+    log::warn,
     reflect::TypePath,
 };
-use cube_core::seed::Seed;
+use cube_core::{seed::Seed, validate::validate};
 use serde::Deserialize;
 
 /////////////////////////////////////////////////////////////////////////////
@@ -50,6 +51,11 @@ impl AssetLoader for SeedsAssetLoader {
                     // level
                     let source = value.try_into::<LevelSource>()?;
                     let target = source.into_seed()?;
+                    target.check()?;
+                    for warning in validate(&target) {
+                        let path = load_context.path().to_string_lossy();
+                        warn!("level {}: {}", path, warning);
+                    }
                     return Ok(LevelSeeds(vec![target]));
                 } else if table.contains_key(INDEX_MARK) {
                     // index
@@ -77,3 +83,283 @@ impl AssetLoader for SeedsAssetLoader {
         &["toml"]
     }
 }
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cube_core::{
+        builder::SeedBuilder,
+        cube::{Kind, Movement, Point, Pursuit},
+        difficulty::estimate,
+        solver::INPUTS,
+        CubeCore,
+    };
+    use std::path::{Path, PathBuf};
+
+    // states explored per level at most, and levels with more are unknown.
+    // a million states take minutes and gigabytes of memory on some levels.
+    const LIMIT: usize = 100_000;
+
+    /// A tutorial level is easier than a long detour. Run the `difficulty`
+    /// example to order every level of the index.
+    #[test]
+    fn difficulty() {
+        let index = index();
+        let score = |name| estimate(&level(&index, name), LIMIT).unwrap().score;
+        assert!(score("introduction") < score("detour"));
+    }
+
+    /// Playing a transformed level must give the transformed result of the
+    /// original level, or some rules are biased towards a direction.
+    #[test]
+    fn equivariance() {
+        const ROUNDS: usize = 256;
+
+        type Transform = (
+            &'static str,
+            Seed,
+            Box<dyn Fn(Point) -> Point>,
+            fn(&Movement) -> Movement,
+        );
+
+        // no shipped level has pursuits yet, and ties must be broken the
+        // same way in every direction.
+        let mut pursuit = SeedBuilder::new(7, 6)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::White, [(3, 3)])
+            .cube(Kind::White, [(5, 1), (5, 2)])
+            .cube(Kind::Red, [(6, 5)])
+            .build()
+            .unwrap();
+        pursuit.cubes[1].pursuit = Some(Pursuit::Chase);
+        pursuit.cubes[2].pursuit = Some(Pursuit::Flee);
+        pursuit.cubes[3].pursuit = Some(Pursuit::Chase);
+
+        let index = index();
+        let levels = index
+            .name_list
+            .iter()
+            .map(|name| (name.as_str(), level(&index, name)));
+        for (name, seed) in levels.chain([("pursuit", pursuit)]) {
+            let (w, h) = (seed.size.width, seed.size.height);
+            let transforms: [Transform; 3] = [
+                (
+                    "rotate90",
+                    seed.rotate90(),
+                    Box::new(move |o| Point::new(h - 1 - o.y, o.x)),
+                    Movement::clockwise,
+                ),
+                (
+                    "flip_horizontal",
+                    seed.flip_horizontal(),
+                    Box::new(move |o| Point::new(w - 1 - o.x, o.y)),
+                    Movement::flip_horizontal,
+                ),
+                (
+                    "flip_vertical",
+                    seed.flip_vertical(),
+                    Box::new(move |o| Point::new(o.x, h - 1 - o.y)),
+                    Movement::flip_vertical,
+                ),
+            ];
+
+            for (transform, other, point, movement) in transforms.iter() {
+                let mut l = CubeCore::new(&seed);
+                let mut r = CubeCore::new(other);
+
+                // xorshift, to play the same inputs in every run
+                let mut random = 0x2545_F491_4F6C_DD1D_u64;
+                for round in 0..ROUNDS {
+                    random ^= random << 13;
+                    random ^= random >> 7;
+                    random ^= random << 17;
+                    let input = INPUTS[(random % INPUTS.len() as u64) as usize];
+
+                    l.commit(input).for_each(drop);
+                    r.commit(input.as_ref().map(movement)).for_each(drop);
+
+                    let mut expected = l
+                        .iter()
+                        .map(|o| {
+                            let movement = o.movement.as_ref().map(movement);
+                            (o.id, o.kind, point(o.position), movement, o.constraint)
+                        })
+                        .collect::<Vec<_>>();
+                    let mut actual = r
+                        .iter()
+                        .map(|o| (o.id, o.kind, o.position, o.movement, o.constraint))
+                        .collect::<Vec<_>>();
+                    expected.sort_unstable_by_key(|o| o.0);
+                    actual.sort_unstable_by_key(|o| o.0);
+                    assert_eq!(
+                        expected, actual,
+                        "level {} is not equivariant under {} at round {}",
+                        name, transform, round
+                    );
+                }
+            }
+        }
+    }
+
+    /// Levels written by `Seed::to_toml` are read back as they are.
+    #[test]
+    fn toml_round_trip() {
+        let index = index();
+        let names = index.name_list.iter().map(String::as_str);
+        for name in names.chain(["debug-slap", "debug-slap-2", "debug-general"]) {
+            let text = level(&index, name).to_toml().unwrap();
+            let source = toml::from_str::<super::super::LevelSource>(&text).unwrap();
+            assert_eq!(source.into_seed().unwrap().to_toml(), Some(text));
+        }
+    }
+
+    /// A known solution of each level in the index, with the checksum of
+    /// its trace. Update a checksum only if rules are meant to change.
+    const REPLAYS: &[(&str, &str, u64)] = &[
+        ("introduction", "LDLLRRRRRR", 0xB737_6B9C_5130_1661),
+        ("rotation", "DRRRUULLDDDRRRR", 0x6AE3_0BAC_47E8_C2AD),
+        ("merge", "DDDRRRRRLLLLUUUUURRRRRRRDD", 0x9C93_D03F_E334_FAF7),
+        (
+            "detour",
+            "URRRDRDRDRRRUUDLLLLRRRDDLLLLLL",
+            0x4DB5_C1A2_4385_81E3,
+        ),
+        ("tetris", "LLLILDDIRRRRRDRID", 0x27FB_3749_C442_8810),
+        (
+            "matrix",
+            "LLDRRRUURDDRDLUURLUUURRULLLD",
+            0xA071_4FBF_5B9C_FB2D,
+        ),
+        ("haircut", "LLDULLLLDDDDDRRRR", 0xBEB6_4852_64CD_4F3E),
+        ("printer", "LLLDLDLDLLLLUUUU", 0xE13B_DC98_7C49_AD44),
+        ("moving-together", "RULLDDLUURULD", 0xF338_8D55_A53B_69BA),
+        (
+            "moving-forest",
+            "RRRRRRDRRRRRLLDDDDDDLLLLUULLLLLLDDDDDDD",
+            0xB2B2_4677_AB8E_1693,
+        ),
+        (
+            "centrosymmetry-and-axisymmetry",
+            "DDRRRRRRULLLRUDLLLLD",
+            0x0BA7_6169_FD6A_B364,
+        ),
+        ("swap", "LDLULLLLLLDRU", 0xB364_E70C_8D5C_9CFD),
+        ("sacrifice", "URRDRRDRRRRRRRRUR", 0x56BB_A5E1_6BE4_3D80),
+        (
+            "reincarnation",
+            "IURRRRRRUIIIIIIIIIII",
+            0xA80F_5C98_CB98_BD3E,
+        ),
+        (
+            "triangle-power",
+            "IIIIIIIIIRRDDRRDDRRUIIIIIIIIIIIIII",
+            0xECE4_2432_80E2_1DCF,
+        ),
+        (
+            "synchronization",
+            "RRIIIIRRRRRRRRRRRR",
+            0x34E3_AF7B_BB25_6220,
+        ),
+        (
+            "now-or-never",
+            "LLLLLDDUUUUUUUDDDDDRLRLRRRRR",
+            0xD47E_12B3_0364_6E2E,
+        ),
+        (
+            "unlock",
+            "IIUULLLLLLLUULLLLLUULLLLUULLDDLIULLUUULLDDI",
+            0x7E90_4CB1_4B80_084E,
+        ),
+    ];
+
+    /// Every shipped level is still solved by its known solution, and every
+    /// round of the solution changes the same things as before.
+    #[test]
+    fn replays() {
+        let index = index();
+        let names = REPLAYS.iter().map(|o| o.0).collect::<Vec<_>>();
+        assert_eq!(names, index.name_list, "levels without replays");
+
+        for &(name, solution, checksum) in REPLAYS {
+            let mut game = CubeCore::new(&level(&index, name));
+            let mut hash = Fnv::new();
+            for input in inputs_of(solution) {
+                assert!(!game.goals().all(|o| o.1), "{} is solved too early", name);
+                for diff in game.commit(input) {
+                    hash.write(diff.id as i64);
+                    hash.write(diff.kind.map_or(-1, |o| o as i64));
+                    hash.write(diff.position.map_or(-1, |o| o.x as i64));
+                    hash.write(diff.position.map_or(-1, |o| o.y as i64));
+                    hash.write(diff.movement.map_or(-1, |o| o.map_or(4, |o| o as i64)));
+                    hash.write(diff.constraint.map_or(-1, |o| o as i64));
+                    hash.write(diff.neighborhood.map_or(-1, |o| {
+                        let bits = o.states().into_iter().enumerate();
+                        bits.map(|(i, o)| (o as i64) << i).sum()
+                    }));
+                    hash.write(diff.hidden.map_or(-1, |o| o as i64));
+                    hash.write(diff.removed as i64);
+                    hash.write(diff.pivot.map_or(-1, |o| o.0.x as i64));
+                    hash.write(diff.pivot.map_or(-1, |o| o.0.y as i64));
+                    hash.write(diff.pivot.map_or(-1, |o| o.1 as i64));
+                }
+                hash.write(i64::MIN); // end of a round
+            }
+            assert!(game.goals().all(|o| o.1), "{} is not solved", name);
+            assert_eq!(
+                hash.0, checksum,
+                "trace of {} is changed to {:#018x}",
+                name, hash.0
+            );
+        }
+    }
+
+    /// Read inputs of `L`, `D`, `U`, `R` and `I` for idle.
+    fn inputs_of(text: &str) -> Vec<Option<Movement>> {
+        text.chars()
+            .map(|c| match c {
+                'L' => Some(Movement::Left),
+                'D' => Some(Movement::Down),
+                'U' => Some(Movement::Up),
+                'R' => Some(Movement::Right),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// FNV-1a, which is stable between builds unlike `DefaultHasher`.
+    struct Fnv(u64);
+
+    impl Fnv {
+        fn new() -> Self {
+            Self(0xCBF2_9CE4_8422_2325)
+        }
+
+        fn write(&mut self, value: i64) {
+            for byte in value.to_le_bytes() {
+                self.0 ^= byte as u64;
+                self.0 = self.0.wrapping_mul(0x0100_0000_01B3);
+            }
+        }
+    }
+
+    fn root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+    }
+
+    fn index() -> LevelIndex {
+        let texts = std::fs::read_to_string(root().join("level/index.toml")).unwrap();
+        toml::from_str::<LevelIndex>(&texts).unwrap()
+    }
+
+    fn level(index: &LevelIndex, name: &str) -> Seed {
+        let path = root()
+            .join(&index.directory)
+            .join([name, ".", &index.extension].concat());
+        let texts = std::fs::read_to_string(path).unwrap();
+        let source = toml::from_str::<super::super::LevelSource>(&texts).unwrap();
+        source.into_seed().unwrap()
+    }
+}
@@ -11,8 +11,8 @@ pub mod system {
     pub use adaption::self_adaption_system as self_adaption;
     pub use state::state_system as state;
     pub use translate::{
-        position_system as position, realpha_system as realpha, recolor_system as recolor,
-        reshape_system as reshape,
+        blink_system as blink, position_system as position, realpha_system as realpha,
+        recolor_system as recolor, reshape_system as reshape, vanish_system as vanish,
     };
 }
 
@@ -3,7 +3,7 @@ use std::time::Duration;
 use bevy::prelude::*;
 use bevy_prototype_lyon::entity::ShapeBundle;
 use bevy_prototype_lyon::prelude::*;
-use cube_core::cube::{Constraint, Kind, Movement, Neighborhood, Point};
+use cube_core::cube::{Constraint, Kind, Movement, Neighborhood, Point, Tile};
 
 use super::{
     super::{model::World, view::ViewMapper},
@@ -21,6 +21,22 @@ struct DestinationBundle {
     color: Fill,
 }
 
+/// Tiled marks a tile on the floor, which is removed once it has been used
+/// up, e.g. a picked key.
+#[derive(Component)]
+pub struct Tiled {
+    pub point: Point,
+}
+
+#[derive(Bundle)]
+struct TileBundle {
+    tiled: Tiled,
+    bound: Earthbound,
+    scale: AutoRescale,
+    shape: ShapeBundle,
+    color: Fill,
+}
+
 #[derive(Component)]
 pub struct Cubic {
     pub id: usize,
@@ -91,6 +107,36 @@ pub fn hello_world(commands: &mut Commands, state: &World, mapper: &ViewMapper)
             .insert(TranslateAlpha::new(0.1, 0.4, Duration::from_secs(4)));
     }
 
+    // create tiles
+    for (point, tile) in state.tiles() {
+        let (points, color) = match tile {
+            Tile::Conveyor(movement) => (style::conveyor_arrow(movement), style::tile_color()),
+            Tile::Paint(kind) => (style::paint_splash(), style::paint_color(kind)),
+            Tile::Cutter(side) => (style::cutter_blade(side), style::tile_color()),
+            Tile::Rotator { clockwise } => (style::rotator_arrow(clockwise), style::tile_color()),
+            Tile::Pit => (style::pit_hole(), style::pit_color()),
+            Tile::Key(kind) => (style::key_shape(), style::cube_color(kind)),
+            Tile::Lock(kind) => (style::keyhole(), style::cube_color(kind)),
+            Tile::Blinker(_) => (style::blinker_base(), style::tile_color()),
+        };
+        // keyholes are drawn on the locks above them.
+        let z = if tile.lock().is_some() { 1.5 } else { 0.5 };
+        commands.spawn(TileBundle {
+            tiled: Tiled { point },
+            bound: Earthbound,
+            scale: AutoRescale { point, offset: 0.5 },
+            shape: make_shape(
+                &shapes::Polygon {
+                    points,
+                    closed: true,
+                },
+                (mapper.locate(&point) + delta).extend(z),
+                Vec3::new(scale, scale, 0.),
+            ),
+            color: Fill::color(color),
+        });
+    }
+
     // create cubes
     let mut boundary_builder = BoundaryBuilder::new(state.width(), state.height());
     for item in state.cubes() {
@@ -116,7 +162,10 @@ pub fn hello_world(commands: &mut Commands, state: &World, mapper: &ViewMapper)
                 (mapper.locate(&item.position) + delta).extend(1.),
                 Vec3::new(scale, scale, 1.),
             ),
-            color: Fill::color(style::cube_color(item.kind)),
+            color: Fill::color(match item.hidden {
+                true => style::cube_color(item.kind).with_a(0.),
+                false => style::cube_color(item.kind),
+            }),
         });
     }
 
@@ -3,8 +3,10 @@ use bevy::prelude::*;
 use super::{
     super::{input::MovementChanged, model::World, scene_running::WorldChanged},
     adaption::AutoRescale,
-    bundle::Cubic,
-    translate::{TranslateColor, TranslatePosition, TranslateShape},
+    bundle::{Cubic, Tiled},
+    translate::{
+        TranslateBlink, TranslateColor, TranslatePosition, TranslateShape, TranslateVanish,
+    },
 };
 
 pub fn state_system(
@@ -12,6 +14,7 @@ pub fn state_system(
     mut input_action: EventReader<MovementChanged>,
     mut change_world: EventWriter<WorldChanged>,
     mut query: Query<(Entity, &mut Cubic, &mut AutoRescale)>,
+    tiled: Query<(Entity, &Tiled), Without<TranslateVanish>>,
     mut world: ResMut<World>,
     mut ticker: Local<detail::Ticker>,
     mut actions: Local<detail::ActionQueue>,
@@ -56,6 +59,16 @@ pub fn state_system(
         });
 
         for (id, mut cube, mut position, diff) in query {
+            // removal, after the last movement
+            if diff.removed {
+                commands.entity(id).insert(TranslateVanish::new(step));
+            }
+
+            // blinkers lowered or raised
+            if let Some(value) = diff.hidden {
+                commands.entity(id).insert(TranslateBlink::new(value, step));
+            }
+
             // color
             if let Some(value) = diff.kind {
                 let component = TranslateColor::new(cube.kind, value, step);
@@ -85,6 +98,13 @@ pub fn state_system(
             }
         }
 
+        // tiles used up, e.g. picked keys
+        for (id, tile) in &tiled {
+            if !world.tiles().any(|(point, _)| point == tile.point) {
+                commands.entity(id).insert(TranslateVanish::new(step));
+            }
+        }
+
         // check status
         *completed = world.done();
     }
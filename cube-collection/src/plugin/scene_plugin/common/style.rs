@@ -13,15 +13,140 @@ pub const fn destnation_color() -> Color {
     Color::GRAY
 }
 
+pub const fn tile_color() -> Color {
+    Color::SILVER
+}
+
+pub const fn pit_color() -> Color {
+    Color::BLACK
+}
+
 pub const fn cube_color(kind: Kind) -> Color {
     match kind {
         Kind::White /* **/ => Color::rgb(1.000, 1.000, 1.000),
         Kind::Red /*   **/ => Color::rgb(0.988, 0.512, 0.512),
         Kind::Blue /*  **/ => Color::rgb(0.582, 0.727, 0.945),
         Kind::Green /* **/ => Color::rgb(0.533, 0.859, 0.425),
+        Kind::Crate /* **/ => Color::rgb(0.761, 0.604, 0.424),
+        Kind::Brick /* **/ => Color::rgb(0.706, 0.427, 0.365),
+        Kind::Bomb /*  **/ => Color::rgb(0.376, 0.376, 0.420),
     }
 }
 
+/// An arrow head pointing to the direction, inside a unit square centered
+/// at (0, 0).
+pub fn conveyor_arrow(direction: Movement) -> Vec<Vec2> {
+    use Movement::*;
+    let rotation = match direction {
+        Left /*  **/ => Vec2::NEG_X,
+        Down /*  **/ => Vec2::NEG_Y,
+        Up /*    **/ => Vec2::Y,
+        Right /* **/ => Vec2::X,
+    };
+    [(-0.15, 0.3), (0.25, 0.), (-0.15, -0.3), (-0.05, 0.)]
+        .into_iter()
+        .map(|o| rotation.rotate(Vec2::from(o)))
+        .collect()
+}
+
+pub fn paint_color(kind: Kind) -> Color {
+    cube_color(kind).with_a(0.6)
+}
+
+/// Three quarters of a ring ending with an arrow head, inside a unit square
+/// centered at (0, 0).
+pub fn rotator_arrow(clockwise: bool) -> Vec<Vec2> {
+    const N: usize = 12;
+    let end = std::f32::consts::PI * 1.5;
+    let at = |radius: f32, i: usize| Vec2::from_angle(end * i as f32 / N as f32) * radius;
+
+    // anticlockwise as y grows upward in the view
+    let mut points = (0..=N).map(|i| at(0.3, i)).collect::<Vec<_>>();
+    let tangent = Vec2::from_angle(end).perp();
+    points.push(at(0.38, N));
+    points.push(at(0.25, N) + tangent * 0.15);
+    points.push(at(0.12, N));
+    points.extend((0..=N).rev().map(|i| at(0.2, i)));
+    if clockwise {
+        points.iter_mut().for_each(|o| o.x = -o.x);
+    }
+    points
+}
+
+/// A thin bar on the side of a unit square centered at (0, 0).
+pub fn cutter_blade(side: Movement) -> Vec<Vec2> {
+    use Movement::*;
+    let rotation = match side {
+        Left /*  **/ => Vec2::NEG_X,
+        Down /*  **/ => Vec2::NEG_Y,
+        Up /*    **/ => Vec2::Y,
+        Right /* **/ => Vec2::X,
+    };
+    [(0.42, 0.5), (0.5, 0.5), (0.5, -0.5), (0.42, -0.5)]
+        .into_iter()
+        .map(|o| rotation.rotate(Vec2::from(o)))
+        .collect()
+}
+
+/// A diamond inside a unit square centered at (0, 0).
+pub fn paint_splash() -> Vec<Vec2> {
+    [(0., 0.3), (0.3, 0.), (0., -0.3), (-0.3, 0.)]
+        .into_iter()
+        .map(Vec2::from)
+        .collect()
+}
+
+/// A square hole inside a unit square centered at (0, 0).
+pub fn pit_hole() -> Vec<Vec2> {
+    [(-0.4, 0.4), (0.4, 0.4), (0.4, -0.4), (-0.4, -0.4)]
+        .into_iter()
+        .map(Vec2::from)
+        .collect()
+}
+
+/// A key with its bow on the left, inside a unit square centered at (0, 0).
+pub fn key_shape() -> Vec<Vec2> {
+    [
+        (-0.35, 0.15),
+        (-0.05, 0.15),
+        (-0.05, 0.05),
+        (0.35, 0.05),
+        (0.35, -0.15),
+        (0.25, -0.15),
+        (0.25, -0.05),
+        (0.15, -0.05),
+        (0.15, -0.15),
+        (-0.05, -0.15),
+        (-0.35, -0.15),
+    ]
+    .into_iter()
+    .map(Vec2::from)
+    .collect()
+}
+
+/// A keyhole inside a unit square centered at (0, 0).
+pub fn keyhole() -> Vec<Vec2> {
+    [
+        (-0.1, 0.25),
+        (0.1, 0.25),
+        (0.1, 0.05),
+        (0.15, -0.25),
+        (-0.15, -0.25),
+        (-0.1, 0.05),
+    ]
+    .into_iter()
+    .map(Vec2::from)
+    .collect()
+}
+
+/// A square under a blinker, inside a unit square centered at (0, 0).
+pub fn blinker_base() -> Vec<Vec2> {
+    [(-0.3, 0.3), (0.3, 0.3), (0.3, -0.3), (-0.3, -0.3)]
+        .into_iter()
+        .map(Vec2::from)
+        .collect()
+}
+
 pub fn cube_boundaries(pattern: Neighborhood, scale: f32) -> Vec<Vec2> {
     let mut points = Vec::with_capacity(12);
 
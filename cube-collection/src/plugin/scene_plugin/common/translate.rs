@@ -119,9 +119,13 @@ pub struct TranslatePosition {
 impl TranslatePosition {
     pub fn make(cube: &Cubic, position: Point, diff: &Diff, duration: Duration) -> Option<Self> {
         if let Some(target) = diff.position {
+            let parameters = match diff.pivot {
+                Some((pivot, clockwise)) => Position::Turn(position, pivot, clockwise),
+                None => Position::Move(position, target),
+            };
             return Some(TranslatePosition {
                 elapse: Timer::new(duration, TimerMode::Once),
-                parameters: Position::Move(position, target),
+                parameters,
             });
         }
 
@@ -150,9 +154,10 @@ impl TranslatePosition {
 
 #[derive(Debug)]
 enum Position {
-    Move(Point, Point),      // (from, to)
-    Spin(Point, Point, f32), // (from, delta, limit)
-    Stop(Point),             // (from)
+    Move(Point, Point),       // (from, to)
+    Spin(Point, Point, f32),  // (from, delta, limit)
+    Stop(Point),              // (from)
+    Turn(Point, Point, bool), // (from, pivot, clockwise)
 }
 
 pub fn position_system(
@@ -175,6 +180,11 @@ pub fn position_system(
                     transform.translation = locate(&to).extend(z);
                     commands.entity(id).remove::<TranslatePosition>();
                 }
+                Turn(from, pivot, clockwise) => {
+                    let to = from.rotate(pivot, clockwise);
+                    transform.translation = locate(&to).extend(z);
+                    commands.entity(id).remove::<TranslatePosition>();
+                }
                 Spin(from, _, _) => {
                     transform.translation = locate(&from).extend(z);
                 }
@@ -204,6 +214,17 @@ pub fn position_system(
                     transform.translation = locate(&from).extend(z);
                     commands.entity(id).remove::<TranslatePosition>();
                 }
+                Turn(from, pivot, clockwise) => {
+                    // turn around the pivot, y grows downward.
+                    let percent = translate.elapse.fraction();
+                    let grid = |o: Point| Vec2::new(o.x as f32, o.y as f32);
+                    let moved = grid(from) - grid(pivot);
+                    let angle = std::f32::consts::FRAC_PI_2 * percent;
+                    let angle = if clockwise { angle } else { -angle };
+                    let current = grid(pivot) + Vec2::from_angle(angle).rotate(moved);
+                    let current = mapper.locate(&current) + mapper.scale(&(0.5, 0.5));
+                    transform.translation = current.extend(z);
+                }
             }
         }
     }
@@ -244,3 +265,70 @@ pub fn realpha_system(mut query: Query<(&mut TranslateAlpha, &mut Fill)>, time:
         draw.color.set_a(alpha);
     }
 }
+
+/// TranslateBlink fades out a unit being hidden, or fades it in again, e.g.
+/// a blinker lowered or raised.
+#[derive(Component, Debug)]
+pub struct TranslateBlink {
+    elapse: Timer,
+    hidden: bool,
+}
+
+impl TranslateBlink {
+    pub fn new(hidden: bool, duration: Duration) -> Self {
+        Self {
+            elapse: Timer::new(duration, TimerMode::Once),
+            hidden,
+        }
+    }
+}
+
+pub fn blink_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut TranslateBlink, &mut Fill)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta();
+    for (id, mut translate, mut draw) in &mut query {
+        let percent = if translate.elapse.tick(delta).finished() {
+            commands.entity(id).remove::<TranslateBlink>();
+            1.
+        } else {
+            translate.elapse.fraction()
+        };
+
+        match translate.hidden {
+            true => draw.color.set_a(1. - percent),
+            false => draw.color.set_a(percent),
+        };
+    }
+}
+
+/// TranslateVanish fades out a removed unit, and then despawns it.
+#[derive(Component, Debug)]
+pub struct TranslateVanish {
+    elapse: Timer,
+}
+
+impl TranslateVanish {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            elapse: Timer::new(duration, TimerMode::Once),
+        }
+    }
+}
+
+pub fn vanish_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut TranslateVanish, &mut Fill)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta();
+    for (id, mut translate, mut draw) in &mut query {
+        if translate.elapse.tick(delta).finished() {
+            commands.entity(id).despawn_recursive();
+        } else {
+            draw.color.set_a(1. - translate.elapse.fraction());
+        }
+    }
+}
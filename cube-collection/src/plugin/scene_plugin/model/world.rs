@@ -3,7 +3,7 @@ use std::{collections::HashMap, time::Duration};
 use bevy::prelude::*;
 use bevy::time::Timer;
 use cube_core::{
-    cube::{Movement, Point},
+    cube::{Movement, Point, Tile},
     seed::Seed,
     Diff, Unit,
 };
@@ -38,6 +38,10 @@ impl World {
         self.state.goals().map(|(point, _)| point)
     }
 
+    pub fn tiles(&self) -> impl Iterator<Item = (Point, Tile)> + '_ {
+        self.state.tiles()
+    }
+
     pub fn step(&self) -> Duration {
         self.timer.duration()
     }
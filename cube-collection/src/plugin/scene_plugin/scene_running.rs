@@ -13,10 +13,12 @@ pub fn setup(app: &mut App) {
         .add_systems(
             Update,
             (
+                system::blink,
                 system::position,
                 system::realpha,
                 system::recolor,
                 system::reshape,
+                system::vanish,
             )
                 .run_if(resource_exists::<model::World>)
                 .before(system::state),
@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+
+use super::{
+    cube::{Adjacence, Constraint, Falling, Kind, Movement, Point},
+    glyph,
+    rule::DisjointSet,
+    seed::{Command, Cube, Info, Seed, Size},
+    state::CubeCore,
+};
+
+/// Board is a comparable picture of a game. It is drawn in three grids in a
+/// frame of `*`: cubes in the glyph alphabet of level files, movements of
+/// units and constraints of units, e.g.
+///
+/// ```text
+/// *************
+/// *GW *>. *S. *
+/// *|  *>  *S  *
+/// *x  *   *   *
+/// *************
+/// ```
+///
+/// Movements are `<`, `v`, `^`, `>` or `.` for none. Constraints are `.` for
+/// free, `S` for stop, `L` for lock or `K` for slap (knocking). Targets under
+/// cubes are not drawn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    pub width: i32,
+    pub height: i32,
+    pub cubes: Vec<Piece>,   // sorted by the first unit
+    pub targets: Vec<Point>, // targets not covered
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Piece {
+    pub kind: Kind,
+    pub body: Vec<Point>, // sorted from top to bottom, left to right
+    pub movement: Option<Movement>,
+    pub constraint: Constraint,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    /// the row is not three grids in a frame
+    Frame(usize),
+    /// the character is unknown at the point
+    Glyph(char, Point),
+    /// nothing to link at the point
+    Unlinkable(Point),
+    /// movements or constraints differ from others of the same cube
+    Inconsistent(Point),
+}
+
+impl std::fmt::Display for BoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use BoardError::*;
+        match self {
+            Frame(row) => write!(f, "row {} is not three grids in a frame", row),
+            Glyph(c, o) => write!(f, "unknown '{}' at ({}, {})", c, o.x, o.y),
+            Unlinkable(o) => write!(f, "nothing to link at ({}, {})", o.x, o.y),
+            Inconsistent(o) => write!(f, "unit at ({}, {}) differs from its cube", o.x, o.y),
+        }
+    }
+}
+
+impl Board {
+    fn new(width: i32, height: i32, mut cubes: Vec<Piece>, mut targets: Vec<Point>) -> Self {
+        let order = |o: &Point| (o.y, o.x);
+        for cube in cubes.iter_mut() {
+            cube.body.sort_unstable_by_key(order);
+        }
+        cubes.sort_unstable_by_key(|cube| cube.body.first().map(order));
+        targets.sort_unstable_by_key(order);
+        Self {
+            width,
+            height,
+            cubes,
+            targets,
+        }
+    }
+}
+
+impl From<&CubeCore> for Board {
+    fn from(game: &CubeCore) -> Self {
+        let units = game.iter().filter(|unit| !unit.hidden).collect::<Vec<_>>();
+        let at = units
+            .iter()
+            .enumerate()
+            .map(|(i, unit)| (unit.position, i))
+            .collect::<HashMap<_, _>>();
+
+        // group units by their neighborhoods
+        let mut group = DisjointSet::new(units.len());
+        for (i, unit) in units.iter().enumerate() {
+            for adjacence in [Adjacence::LEFT, Adjacence::TOP] {
+                let other = unit.position + adjacence.into();
+                if let (true, Some(&j)) = (unit.neighborhood.has(adjacence), at.get(&other)) {
+                    if units[j].kind == unit.kind {
+                        group.join(i, j);
+                    }
+                }
+            }
+        }
+
+        let mut cubes = HashMap::<usize, Piece>::new();
+        for (i, unit) in units.iter().enumerate() {
+            cubes
+                .entry(group.find(i))
+                .or_insert_with(|| Piece {
+                    kind: unit.kind,
+                    body: Vec::new(),
+                    movement: unit.movement,
+                    constraint: unit.constraint,
+                })
+                .body
+                .push(unit.position);
+        }
+
+        let targets = game.goals().filter(|o| !o.1).map(|o| o.0).collect();
+        let (w, h) = (game.width() as i32, game.height() as i32);
+        Self::new(w, h, cubes.into_values().collect(), targets)
+    }
+}
+
+/// A seed starting from the board. Cubes with a movement get a command of
+/// one step, so that they move that way in the next round. Constraints are
+/// ignored.
+impl From<&Board> for Seed {
+    fn from(board: &Board) -> Self {
+        let cubes = board.cubes.iter().map(|cube| Cube {
+            kind: cube.kind,
+            body: cube.body.clone(),
+            command: cube.movement.map(|movement| Command {
+                is_loop: false,
+                movements: vec![(Some(movement), 1)],
+            }),
+            mirror: None,
+            pursuit: None,
+        });
+        Seed {
+            info: Info {
+                title: "board".into(),
+                author: "board".into(),
+            },
+            size: Size {
+                width: board.width,
+                height: board.height,
+            },
+            cubes: cubes.collect(),
+            destnations: board.targets.clone(),
+            tiles: Vec::new(),
+            gravity: None,
+            falling: Falling::Whole,
+        }
+    }
+}
+
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (w, h) = (self.width.max(0) as usize, self.height.max(0) as usize);
+        let Some(cubes) = glyph::draw(
+            self.width,
+            self.height,
+            self.cubes
+                .iter()
+                .map(|cube| (cube.kind, cube.body.as_slice())),
+            &self.targets,
+        ) else {
+            return writeln!(f, "<overlapped>");
+        };
+
+        let mut movements = vec![vec![' '; w]; h];
+        let mut constraints = vec![vec![' '; w]; h];
+        for cube in self.cubes.iter() {
+            for o in cube.body.iter() {
+                let (x, y) = (o.x as usize, o.y as usize);
+                movements[y][x] = match cube.movement {
+                    None => '.',
+                    Some(Movement::Left) => '<',
+                    Some(Movement::Down) => 'v',
+                    Some(Movement::Up) => '^',
+                    Some(Movement::Right) => '>',
+                };
+                constraints[y][x] = match cube.constraint {
+                    Constraint::Free => '.',
+                    Constraint::Stop => 'S',
+                    Constraint::Lock => 'L',
+                    Constraint::Slap => 'K',
+                };
+            }
+        }
+
+        let frame = "*".repeat(w * 3 + 4);
+        writeln!(f, "{}", frame)?;
+        for y in 0..h {
+            let row = |grid: &[Vec<char>]| String::from_iter(&grid[y]);
+            let (l, m, r) = (row(&cubes), row(&movements), row(&constraints));
+            writeln!(f, "*{}*{}*{}*", l, m, r)?;
+        }
+        writeln!(f, "{}", frame)
+    }
+}
+
+impl std::str::FromStr for Board {
+    type Err = BoardError;
+
+    /// Read a board drawn by `Display`. Lines around rows are ignored if
+    /// they are blank or made of `*` only.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use BoardError::*;
+
+        let rows = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.chars().all(|c| c == '*'))
+            .enumerate()
+            .map(|(y, line)| {
+                let grids = line.split('*').collect::<Vec<_>>();
+                match grids.as_slice() {
+                    ["", l, m, r, ""] if l.len() == m.len() && m.len() == r.len() => {
+                        Ok([*l, *m, *r].map(|o| o.chars().collect::<Vec<_>>()))
+                    }
+                    _ => Err(Frame(y)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = rows.first().map(|row| row[0].len()).unwrap_or_default();
+        if let Some(y) = rows.iter().position(|row| row[0].len() != width) {
+            return Err(Frame(y));
+        }
+
+        // cubes, in the same way as level files
+        let mut group = DisjointSet::new(width * rows.len());
+        let mut kinds = Vec::<Kind>::new();
+        let mut owner = vec![vec![None; width]; rows.len()];
+        let mut targets = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row[0].iter().enumerate() {
+                let o = Point::new(x as i32, y as i32);
+                let left = x.checked_sub(1).and_then(|x| owner[y][x]);
+                let upper = y.checked_sub(1).and_then(|y| owner[y][x]);
+                let mut make = |kind| {
+                    kinds.push(kind);
+                    Some(kinds.len() - 1)
+                };
+                owner[y][x] = match c {
+                    ' ' => None,
+                    'x' | 'X' => {
+                        targets.push(o);
+                        None
+                    }
+                    'W' | 'w' => make(Kind::White),
+                    'R' | 'r' => make(Kind::Red),
+                    'B' | 'b' => make(Kind::Blue),
+                    'G' | 'g' => make(Kind::Green),
+                    'C' | 'c' => make(Kind::Crate),
+                    'K' | 'k' => make(Kind::Brick),
+                    'O' | 'o' => make(Kind::Bomb),
+                    '-' => Some(left.ok_or(Unlinkable(o))?),
+                    '|' => Some(upper.ok_or(Unlinkable(o))?),
+                    '/' => match (left, upper) {
+                        (Some(l), Some(u)) if kinds[l] == kinds[u] => {
+                            group.join(u, l);
+                            Some(l)
+                        }
+                        _ => return Err(Unlinkable(o)),
+                    },
+                    _ => return Err(Glyph(c, o)),
+                };
+            }
+        }
+
+        // movements and constraints of each cube
+        let mut cubes = HashMap::<usize, Piece>::new();
+        for (y, row) in rows.iter().enumerate() {
+            for x in 0..width {
+                let o = Point::new(x as i32, y as i32);
+                let (m, c) = (row[1][x], row[2][x]);
+                let Some(i) = owner[y][x] else {
+                    match (m, c) {
+                        (' ', ' ') => continue,
+                        _ => return Err(Inconsistent(o)),
+                    }
+                };
+                let movement = match m {
+                    '.' => None,
+                    '<' => Some(Movement::Left),
+                    'v' => Some(Movement::Down),
+                    '^' => Some(Movement::Up),
+                    '>' => Some(Movement::Right),
+                    _ => return Err(Glyph(m, o)),
+                };
+                let constraint = match c {
+                    '.' => Constraint::Free,
+                    'S' => Constraint::Stop,
+                    'L' => Constraint::Lock,
+                    'K' => Constraint::Slap,
+                    _ => return Err(Glyph(c, o)),
+                };
+
+                let i = group.find(i);
+                let cube = cubes.entry(i).or_insert_with(|| Piece {
+                    kind: kinds[i],
+                    body: Vec::new(),
+                    movement,
+                    constraint,
+                });
+                if cube.movement != movement || cube.constraint != constraint {
+                    return Err(Inconsistent(o));
+                }
+                cube.body.push(o);
+            }
+        }
+
+        let (w, h) = (width as i32, rows.len() as i32);
+        Ok(Self::new(w, h, cubes.into_values().collect(), targets))
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_and_write() {
+        let text = "
+            ****************
+            *GxG *> > *S S *
+            *|-/ *>>> *SSS *
+            *  R-*  ^^*  LL*
+            *WR/ *.^^ *.LL *
+            ****************
+        ";
+        let board = text.parse::<Board>().unwrap();
+        assert_eq!(board.width, 4);
+        assert_eq!(board.height, 4);
+        assert_eq!(board.targets, [Point::new(1, 0)]);
+        assert_eq!(board.cubes.len(), 3);
+        assert_eq!(board.cubes[0].kind, Kind::Green);
+        assert_eq!(board.cubes[0].body.len(), 5);
+        assert_eq!(board.cubes[1].movement, Some(Movement::Up));
+        assert_eq!(board.cubes[1].constraint, Constraint::Lock);
+        assert_eq!(board.to_string().parse::<Board>(), Ok(board.clone()));
+        assert_eq!(board.to_string().lines().nth(1), Some("*GxG *> > *S S *"));
+
+        use BoardError::*;
+        let error = |text: &str| text.parse::<Board>().unwrap_err();
+        assert_eq!(error("*G*>*S"), Frame(0));
+        assert_eq!(error("*G*>*S*\n*GG*..*..*"), Frame(1));
+        assert_eq!(error("*Q*.*.*"), Glyph('Q', Point::new(0, 0)));
+        assert_eq!(error("*-*.*.*"), Unlinkable(Point::new(0, 0)));
+        assert_eq!(error("*R*.*.*\n*/*.*.*"), Unlinkable(Point::new(0, 1)));
+        assert_eq!(error("*G-*.>*..*"), Inconsistent(Point::new(1, 0)));
+        assert_eq!(error("*G *..*.S*"), Inconsistent(Point::new(1, 0)));
+    }
+}
@@ -0,0 +1,209 @@
+use super::{
+    cube::{Falling, Kind, Movement, Point, Tile},
+    seed::{Command, Cube, Info, Seed, SeedError, Size},
+};
+
+/// SeedBuilder makes seeds in code rather than in level files, e.g.
+///
+/// ```
+/// use cube_core::{builder::{Rect, SeedBuilder}, cube::Kind};
+///
+/// let seed = SeedBuilder::new(4, 3)
+///     .wall(Rect::new(0, 2, 4, 1))
+///     .cube(Kind::Green, [(0, 0), (1, 0)])
+///     .target((3, 1))
+///     .build()
+///     .unwrap();
+/// assert_eq!(seed.cubes.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SeedBuilder {
+    seed: Seed,
+    scripts: Vec<(Vec<Point>, Command)>, // bound when building
+}
+
+/// A rectangle from its top left point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub const fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn points(self) -> impl Iterator<Item = Point> {
+        let xs = self.x..self.x + self.width;
+        (self.y..self.y + self.height).flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+    }
+}
+
+impl SeedBuilder {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            seed: Seed {
+                info: Info {
+                    title: String::new(),
+                    author: String::new(),
+                },
+                size: Size { width, height },
+                cubes: Vec::new(),
+                destnations: Vec::new(),
+                tiles: Vec::new(),
+                gravity: None,
+                falling: Falling::Whole,
+            },
+            scripts: Vec::new(),
+        }
+    }
+
+    pub fn info(mut self, title: impl Into<String>, author: impl Into<String>) -> Self {
+        self.seed.info = Info {
+            title: title.into(),
+            author: author.into(),
+        };
+        self
+    }
+
+    /// Add a white cube filling the rectangle, which never moves.
+    pub fn wall(self, rect: Rect) -> Self {
+        self.cube(Kind::White, rect.points())
+    }
+
+    /// Add a cube of the points.
+    pub fn cube(mut self, kind: Kind, points: impl IntoIterator<Item = impl Into<Point>>) -> Self {
+        self.seed.cubes.push(Cube {
+            kind,
+            body: points.into_iter().map(Into::into).collect(),
+            command: None,
+            mirror: None,
+            pursuit: None,
+        });
+        self
+    }
+
+    pub fn target(mut self, point: impl Into<Point>) -> Self {
+        self.seed.destnations.push(point.into());
+        self
+    }
+
+    /// Put the tile on the floor at the point.
+    pub fn tile(mut self, point: impl Into<Point>, tile: Tile) -> Self {
+        self.seed.tiles.push((point.into(), tile));
+        self
+    }
+
+    /// Pull every cube without other movements towards the direction.
+    pub fn gravity(mut self, movement: Movement) -> Self {
+        self.seed.gravity = Some(movement);
+        self
+    }
+
+    /// Choose how cubes fall into pits.
+    pub fn falling(mut self, falling: Falling) -> Self {
+        self.seed.falling = falling;
+        self
+    }
+
+    /// Bind the command to cubes at the points, in the same way as
+    /// `binding` in level files.
+    pub fn script(
+        mut self,
+        points: impl IntoIterator<Item = impl Into<Point>>,
+        command: Command,
+    ) -> Self {
+        let points = points.into_iter().map(Into::into).collect();
+        self.scripts.push((points, command));
+        self
+    }
+
+    pub fn build(self) -> Result<Seed, SeedError> {
+        let Self { mut seed, scripts } = self;
+        for (points, command) in scripts {
+            for o in points {
+                match seed.cubes.iter_mut().find(|cube| cube.body.contains(&o)) {
+                    Some(cube) => cube.command = Some(command.clone()),
+                    None => return Err(SeedError::Unbound(o)),
+                }
+            }
+        }
+        seed.check().map(|_| seed)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        /******
+         *GG x*
+         *  B *
+         *WWWW*
+         ******/
+
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Left), 2), (None, 1)],
+        };
+        let builder = SeedBuilder::new(4, 3)
+            .info("test", "test")
+            .wall(Rect::new(0, 2, 4, 1))
+            .cube(Kind::Green, [(0, 0), (1, 0)])
+            .cube(Kind::Blue, [(2, 1)])
+            .target((3, 0))
+            .script([(2, 1)], command.clone());
+
+        let seed = builder.clone().build().unwrap();
+        assert_eq!(seed.info.title, "test");
+        assert_eq!(seed.cubes.len(), 3);
+        assert_eq!(seed.cubes[0].body.len(), 4);
+        assert_eq!(seed.cubes[2].command, Some(command.clone()));
+        assert_eq!(seed.destnations, [Point::new(3, 0)]);
+        let seed = builder.clone().gravity(Movement::Down).build().unwrap();
+        assert_eq!(seed.gravity, Some(Movement::Down));
+
+        use SeedError::*;
+        let error = |builder: SeedBuilder| builder.build().unwrap_err();
+        let size = Size {
+            width: 0,
+            height: 3,
+        };
+        assert_eq!(error(SeedBuilder::new(0, 3)), BadSize(size));
+        let empty = [(0, 0); 0];
+        assert_eq!(error(builder.clone().cube(Kind::Red, empty)), EmptyCube(3));
+        let outside = builder.clone().cube(Kind::Red, [(4, 1)]);
+        assert_eq!(error(outside), Outside(Point::new(4, 1)));
+        let outside = builder.clone().target((-1, 0));
+        assert_eq!(error(outside), Outside(Point::new(-1, 0)));
+        let conveyor = Tile::Conveyor(Movement::Up);
+        let outside = builder.clone().tile((0, 3), conveyor);
+        assert_eq!(error(outside), Outside(Point::new(0, 3)));
+        let overlapped = builder
+            .clone()
+            .tile((1, 1), conveyor)
+            .tile((1, 1), conveyor);
+        assert_eq!(error(overlapped), Overlapped(Point::new(1, 1)));
+        let overlapped = builder.clone().cube(Kind::Red, [(1, 1), (1, 0)]);
+        assert_eq!(error(overlapped), Overlapped(Point::new(1, 0)));
+        let locked = builder.clone().tile((1, 0), Tile::Lock(Kind::Red));
+        assert_eq!(error(locked), Overlapped(Point::new(1, 0)));
+        let apart = builder.clone().cube(Kind::Red, [(0, 1), (3, 1)]);
+        assert_eq!(error(apart), Disconnected(Point::new(3, 1)));
+        let unbound = builder.script([(3, 1)], command);
+        assert_eq!(error(unbound), Unbound(Point::new(3, 1)));
+    }
+}
@@ -1,12 +1,18 @@
 mod kind;
+mod mirror;
 mod motion;
 mod movement;
 mod neighborhood;
 mod point;
+mod pursuit;
+mod tile;
 
-pub(crate) use motion::{Agreement, Motion};
+pub(crate) use motion::{Agreement, Motion, Sight};
 
 pub use kind::Kind;
+pub use mirror::Mirror;
 pub use movement::{Constraint, Movement};
 pub use neighborhood::{Adjacence, Neighborhood};
 pub use point::Point;
+pub use pursuit::Pursuit;
+pub use tile::{Blink, Falling, Tile};
@@ -4,9 +4,22 @@ pub enum Kind {
     Green,
     Blue,
     Red,
+    Crate, // pushed by others, never merges or absorbs
+    Brick, // a wall broken by bombs, never moves
+    Bomb,  // pushed like crates, breaks bricks next to it when knocking
 }
 
 impl Kind {
+    /// Whether cubes of the kind are pushed by moving cubes.
+    pub const fn pushable(self) -> bool {
+        matches!(self, Kind::Crate | Kind::Bomb)
+    }
+
+    /// Whether cubes of the kind are recolored by paint tiles.
+    pub const fn paintable(self) -> bool {
+        matches!(self, Kind::Red | Kind::Green | Kind::Blue)
+    }
+
     pub const fn absorbable(self, other: Self) -> bool {
         use Kind::*;
         match self {
@@ -14,6 +27,7 @@ impl Kind {
             Green => matches!(other, Blue),
             Blue => matches!(other, Red),
             Red => matches!(other, Green),
+            Crate | Brick | Bomb => false,
         }
     }
 
@@ -24,6 +38,7 @@ impl Kind {
             Green => matches!(other, Green),
             Blue => matches!(other, Blue),
             Red => matches!(other, Red),
+            Crate | Brick | Bomb => false,
         }
     }
 }
@@ -0,0 +1,48 @@
+use super::Movement;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Mirror {
+    /// move against the input
+    Opposite,
+    /// swap left and right
+    Horizontal,
+    /// swap up and down
+    Vertical,
+    /// turn the input 90° clockwise
+    Clockwise,
+    /// turn the input 90° anticlockwise
+    Anticlockwise,
+}
+
+impl Mirror {
+    pub fn reflect(self, movement: Movement) -> Movement {
+        use Mirror::*;
+        match self {
+            Opposite /*      **/ => movement.opposite(),
+            Horizontal /*    **/ => movement.flip_horizontal(),
+            Vertical /*      **/ => movement.flip_vertical(),
+            Clockwise /*     **/ => movement.clockwise(),
+            Anticlockwise /* **/ => movement.anticlockwise(),
+        }
+    }
+
+    /// The same mirror seen on a board turned 90° clockwise.
+    pub fn rotate90(self) -> Self {
+        use Mirror::*;
+        match self {
+            Horizontal /* **/ => Vertical,
+            Vertical /*   **/ => Horizontal,
+            other /*      **/ => other,
+        }
+    }
+
+    /// The same mirror seen on a board flipped in either axis.
+    pub fn flip(self) -> Self {
+        use Mirror::*;
+        match self {
+            Clockwise /*     **/ => Anticlockwise,
+            Anticlockwise /* **/ => Clockwise,
+            other /*         **/ => other,
+        }
+    }
+}
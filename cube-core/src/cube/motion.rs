@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use super::Movement;
+use super::{Mirror, Movement, Point, Pursuit};
 
 /////////////////////////////////////////////////////////////////////////////
 // export
@@ -41,7 +41,15 @@ impl Agreement {
     }
 }
 
-#[derive(Clone, Debug)]
+/// A read-only view of the board in the current round.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sight<'a> {
+    pub input: Option<Movement>, // movement from player
+    pub body: &'a [Point],       // units of the cube who owns the motion
+    pub targets: &'a [Point],    // units that are chased or fled from
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Motion(Any);
 
 impl Motion {
@@ -60,13 +68,45 @@ impl Motion {
         }))
     }
 
-    pub fn from_iter(others: impl Iterator<Item = Self>) -> Self {
-        let others = others
-            .map(|x| x.0)
-            .filter(|x| !matches!(x, Any::Stop))
-            .collect::<Vec<_>>();
+    pub fn from_mirror(mirror: Mirror) -> Self {
+        Motion(Any::Echo(mirror))
+    }
+
+    pub fn from_pursuit(pursuit: Pursuit) -> Self {
+        Motion(Any::Seek(pursuit))
+    }
+
+    /// Join motions of cubes, each with indexes of the units it drives,
+    /// which may be empty before units are numbered. Members of teams keep
+    /// their own units if they have any.
+    pub fn from_iter(others: impl Iterator<Item = (Self, Vec<usize>)>) -> Self {
+        let mut members = Vec::new();
+        for (other, units) in others {
+            let units = Arc::<[usize]>::from(units);
+            match other.0 {
+                Any::Stop => {}
+                Any::Team(x) => members.extend(x.0.into_iter().map(|(one, own)| match own.len() {
+                    0 => (one, Arc::clone(&units)),
+                    _ => (one, own),
+                })),
+                x => members.push((x, units)),
+            }
+        }
 
-        Motion(Any::Team(Team(others)).slim())
+        Motion(Any::Team(Team(members)).slim())
+    }
+
+    /// The part of the motion driving any of the units, which is the whole
+    /// motion unless it is a team. Members without units drive all.
+    pub fn split(&self, units: &[usize]) -> Self {
+        let drives = |own: &[usize]| own.is_empty() || own.iter().any(|i| units.contains(i));
+        match &self.0 {
+            Any::Team(x) => {
+                let members = x.0.iter().filter(|one| drives(&one.1)).cloned();
+                Motion(Any::Team(Team(members.collect())).slim())
+            }
+            x => Motion(x.clone()),
+        }
     }
 
     pub fn is_stopped(&self) -> bool {
@@ -84,22 +124,22 @@ impl Motion {
     }
 }
 
-impl Iterator for Motion {
-    type Item = Option<Movement>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl Motion {
+    pub fn next(&mut self, sight: &Sight) -> Option<Option<Movement>> {
         self.0 = self.take_inner().slim();
-        self.0.next()
+        self.0.next(sight)
     }
 }
 
 /////////////////////////////////////////////////////////////////////////////
 // internal
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 enum Any {
     Stop,
     Move(Move),
+    Echo(Mirror),
+    Seek(Pursuit),
     Team(Team),
 }
 
@@ -108,27 +148,25 @@ impl Any {
         match self {
             Any::Team(x) if x.0.len() < 2 => match x.0.len() {
                 0 => Any::Stop,
-                1 => x.0.into_iter().next().unwrap(),
+                1 => x.0.into_iter().next().unwrap().0,
                 _ => Any::Team(x),
             },
             _ => self,
         }
     }
-}
-
-impl Iterator for Any {
-    type Item = Option<Movement>;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next(&mut self, sight: &Sight) -> Option<Option<Movement>> {
         match self {
             Any::Stop => None,
             Any::Move(x) => x.next(),
-            Any::Team(x) => x.next(),
+            Any::Echo(x) => Some(sight.input.map(|movement| x.reflect(movement))),
+            Any::Seek(x) => Some(x.decide(sight.body, sight.targets)),
+            Any::Team(x) => x.next(sight),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Move {
     source: Arc<Sequence>,
     primary: usize,
@@ -161,15 +199,13 @@ impl Iterator for Move {
     }
 }
 
-#[derive(Clone, Debug)]
-struct Team(Vec<Any>);
-
-impl Iterator for Team {
-    type Item = Option<Movement>;
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct Team(Vec<(Any, Arc<[usize]>)>); // members with units they drive
 
-    fn next(&mut self) -> Option<Self::Item> {
+impl Team {
+    fn next(&mut self, sight: &Sight) -> Option<Option<Movement>> {
         let mut vote = Agreement::new();
-        self.0.retain_mut(|one| match one.next() {
+        self.0.retain_mut(|(one, _)| match one.next(sight) {
             None => false,
             Some(choice) => {
                 vote.submit(choice);
@@ -187,7 +223,7 @@ impl Iterator for Team {
 /////////////////////////////////////////////////////////////////////////////
 // internal - Sequence
 
-#[derive(Debug)]
+#[derive(Debug, Hash, PartialEq, Eq)]
 struct Sequence {
     looping: bool,
     actions: Box<[(Option<Movement>, usize)]>,
@@ -209,26 +245,33 @@ impl Sequence {
 mod tests {
     use super::*;
 
+    fn input(movement: Movement) -> Sight<'static> {
+        Sight {
+            input: Some(movement),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn single_motion() {
         let mut stop = Motion::new();
-        assert_eq!(stop.next(), None);
+        assert_eq!(stop.next(&Sight::default()), None);
 
         let list = [(None, 2), (Some(Movement::Up), 2)];
         let mut list = Motion::from_sequence(false, list.into_iter());
-        assert_eq!(list.next(), Some(None));
-        assert_eq!(list.next(), Some(None));
-        assert_eq!(list.next(), Some(Some(Movement::Up)));
-        assert_eq!(list.next(), Some(Some(Movement::Up)));
-        assert_eq!(list.next(), None);
+        assert_eq!(list.next(&Sight::default()), Some(None));
+        assert_eq!(list.next(&Sight::default()), Some(None));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Up)));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Up)));
+        assert_eq!(list.next(&Sight::default()), None);
 
         let list = [(Some(Movement::Left), 1), (Some(Movement::Up), 1)];
         let mut list = Motion::from_sequence(true, list.into_iter());
-        assert_eq!(list.next(), Some(Some(Movement::Left)));
-        assert_eq!(list.next(), Some(Some(Movement::Up)));
-        assert_eq!(list.next(), Some(Some(Movement::Left)));
-        assert_eq!(list.next(), Some(Some(Movement::Up)));
-        assert_eq!(list.next(), Some(Some(Movement::Left)));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Left)));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Up)));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Left)));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Up)));
+        assert_eq!(list.next(&Sight::default()), Some(Some(Movement::Left)));
     }
 
     #[test]
@@ -245,13 +288,86 @@ mod tests {
                 [(Some(Movement::Left), 1), (Some(Movement::Up), 1)].into_iter(),
             ),
         ];
-        let mut team = Motion::from_iter(team.into_iter());
-        assert_eq!(team.next(), Some(None));
-        assert_eq!(team.next(), Some(None));
-        assert_eq!(team.next(), Some(None));
-        assert_eq!(team.next(), Some(Some(Movement::Up)));
-        assert_eq!(team.next(), Some(Some(Movement::Left)));
-        assert_eq!(team.next(), Some(Some(Movement::Up)));
+        let mut team = Motion::from_iter(team.into_iter().map(|one| (one, vec![])));
+        assert_eq!(team.next(&Sight::default()), Some(None));
+        assert_eq!(team.next(&Sight::default()), Some(None));
+        assert_eq!(team.next(&Sight::default()), Some(None));
+        assert_eq!(team.next(&Sight::default()), Some(Some(Movement::Up)));
+        assert_eq!(team.next(&Sight::default()), Some(Some(Movement::Left)));
+        assert_eq!(team.next(&Sight::default()), Some(Some(Movement::Up)));
         assert!(matches!(team, Motion(Any::Move(_))));
     }
+
+    #[test]
+    fn mirror_motion() {
+        let mut echo = Motion::from_mirror(Mirror::Opposite);
+        assert_eq!(echo.next(&Sight::default()), Some(None));
+        assert_eq!(echo.next(&input(Movement::Up)), Some(Some(Movement::Down)));
+        assert_eq!(
+            echo.next(&input(Movement::Left)),
+            Some(Some(Movement::Right))
+        );
+
+        let mut echo = Motion::from_mirror(Mirror::Clockwise);
+        assert_eq!(echo.next(&input(Movement::Up)), Some(Some(Movement::Right)));
+        assert_eq!(
+            echo.next(&input(Movement::Right)),
+            Some(Some(Movement::Down))
+        );
+
+        let team = [
+            Motion::from_mirror(Mirror::Horizontal),
+            Motion::from_mirror(Mirror::Vertical),
+            Motion::from_sequence(false, [(Some(Movement::Up), 1)].into_iter()),
+        ];
+        let mut team = Motion::from_iter(team.into_iter().map(|one| (one, vec![])));
+        assert_eq!(team.next(&input(Movement::Left)), Some(None));
+        assert_eq!(team.next(&input(Movement::Up)), Some(None));
+        assert_eq!(team.next(&input(Movement::Left)), Some(None));
+        assert_eq!(team.next(&input(Movement::Down)), Some(None));
+        assert_eq!(team.next(&Sight::default()), Some(None));
+
+        let team = [
+            Motion::from_mirror(Mirror::Horizontal),
+            Motion::from_mirror(Mirror::Opposite),
+        ];
+        let mut team = Motion::from_iter(team.into_iter().map(|one| (one, vec![])));
+        assert_eq!(
+            team.next(&input(Movement::Left)),
+            Some(Some(Movement::Right))
+        );
+        assert_eq!(team.next(&input(Movement::Up)), Some(None));
+    }
+
+    #[test]
+    fn pursuit_motion() {
+        let body = [Point::new(2, 2), Point::new(3, 2)];
+        let targets = [Point::new(3, 2), Point::new(0, 0)];
+        let sight = Sight {
+            input: Some(Movement::Down),
+            body: &body,
+            targets: &targets,
+        };
+
+        let mut chase = Motion::from_pursuit(Pursuit::Chase);
+        assert_eq!(chase.next(&sight), Some(Some(Movement::Left)));
+        let mut flee = Motion::from_pursuit(Pursuit::Flee);
+        assert_eq!(flee.next(&sight), Some(Some(Movement::Right)));
+
+        // cubes wait rather than pick one of equal movements.
+        let body = [Point::new(1, 1)];
+        let sight = Sight {
+            body: &body,
+            targets: &targets[1..],
+            ..sight
+        };
+        assert_eq!(chase.next(&sight), Some(None));
+
+        let sight = Sight {
+            targets: &[],
+            ..sight
+        };
+        assert_eq!(chase.next(&sight), Some(None));
+        assert_eq!(flee.next(&sight), Some(None));
+    }
 }
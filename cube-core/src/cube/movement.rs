@@ -26,6 +26,44 @@ impl Movement {
         }
     }
 
+    pub fn flip_horizontal(&self) -> Self {
+        use Movement::*;
+        match self {
+            Left /*  **/ => Right,
+            Right /* **/ => Left,
+            other /* **/ => *other,
+        }
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        use Movement::*;
+        match self {
+            Down /*  **/ => Up,
+            Up /*    **/ => Down,
+            other /* **/ => *other,
+        }
+    }
+
+    pub fn clockwise(&self) -> Self {
+        use Movement::*;
+        match self {
+            Left /*  **/ => Up,
+            Down /*  **/ => Left,
+            Up  /*   **/ => Right,
+            Right /* **/ => Down,
+        }
+    }
+
+    pub fn anticlockwise(&self) -> Self {
+        use Movement::*;
+        match self {
+            Left /*  **/ => Down,
+            Down /*  **/ => Right,
+            Up  /*   **/ => Left,
+            Right /* **/ => Up,
+        }
+    }
+
     pub fn opposite_to(&self, other: Self) -> bool {
         self.opposite() == other
     }
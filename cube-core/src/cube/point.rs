@@ -30,6 +30,21 @@ impl<T> Point<T> {
     }
 }
 
+impl Point {
+    pub const fn manhattan(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Turn the point a quarter around the pivot, where y grows downward.
+    pub const fn rotate(self, pivot: Self, clockwise: bool) -> Self {
+        let (x, y) = (self.x - pivot.x, self.y - pivot.y);
+        match clockwise {
+            true => Self::new(pivot.x - y, pivot.y + x),
+            false => Self::new(pivot.x + y, pivot.y - x),
+        }
+    }
+}
+
 impl<T> Add for Point<T>
 where
     T: Add<Output = T>,
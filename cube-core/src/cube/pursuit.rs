@@ -0,0 +1,52 @@
+use super::{Movement, Point};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Pursuit {
+    /// step toward the nearest target
+    Chase,
+    /// step away from the nearest target
+    Flee,
+}
+
+impl Pursuit {
+    /// Pick the movement which makes the Manhattan distance between `body`
+    /// and its nearest target shorter (or longer). Ties are broken by the
+    /// sum of squared Euclidean distances between all units and targets, and
+    /// `None` is returned if no movement helps or some are still tied, so
+    /// that decisions never depend on the order of directions.
+    pub fn decide(self, body: &[Point], targets: &[Point]) -> Option<Movement> {
+        let targets = targets
+            .iter()
+            .filter(|target| !body.contains(target))
+            .collect::<Vec<_>>();
+        // smaller is better, whether chasing or fleeing
+        let distance = |delta: Point| {
+            let pairs = body
+                .iter()
+                .flat_map(|&unit| targets.iter().map(move |&&o| (unit + delta, o)));
+            let nearest = pairs.clone().map(|(unit, o)| unit.manhattan(o)).min()?;
+            let spread = pairs
+                .map(|(unit, o)| (unit.x - o.x).pow(2) + (unit.y - o.y).pow(2))
+                .sum::<i32>();
+            Some(match self {
+                Pursuit::Chase => (nearest, spread),
+                Pursuit::Flee => (-nearest, -spread),
+            })
+        };
+
+        let stay = distance(Point::new(0, 0))?.0;
+        let mut moves = Vec::with_capacity(Movement::ALL.len());
+        for movement in Movement::ALL {
+            let value = distance(movement.into())?;
+            if value.0 < stay {
+                moves.push((value, movement));
+            }
+        }
+        moves.sort_unstable_by_key(|o| o.0);
+        match moves.as_slice() {
+            [first, second, ..] if first.0 == second.0 => None,
+            [first, ..] => Some(first.1),
+            [] => None,
+        }
+    }
+}
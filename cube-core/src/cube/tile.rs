@@ -0,0 +1,129 @@
+use super::{Kind, Movement};
+
+/// Floor cells which act on cubes standing on them. Cubes move over tiles
+/// freely, except locks until they are opened and blinkers while raised.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Tile {
+    /// push cubes on it one step each round, unless they move by themselves
+    Conveyor(Movement),
+    /// recolor cubes which move onto it
+    Paint(Kind),
+    /// turn cubes which come to rest on it a quarter around it
+    Rotator { clockwise: bool },
+    /// cut cubes which move onto it along the line of its side
+    Cutter(Movement),
+    /// remove units which move onto it from the game, see `Falling`
+    Pit,
+    /// picked up by a cube of the kind covering it, which opens locks
+    Key(Kind),
+    /// a wall opened for good once the key of the kind is picked up
+    Lock(Kind),
+    /// a wall raised and lowered by rounds, see `Blink`
+    Blinker(Blink),
+}
+
+/// When a blinker is raised. Rounds are counted from the start, and the
+/// wall is raised for the first half of every `period` rounds, rounded up,
+/// as if `phase` rounds had passed before the start.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Blink {
+    pub period: u32,
+    pub phase: u32,
+}
+
+impl Blink {
+    /// Whether the wall is raised in the round, even if a cube covers it.
+    pub fn raised(self, round: usize) -> bool {
+        let period = self.period.max(1) as usize;
+        (round + self.phase as usize) % period < period.div_ceil(2)
+    }
+}
+
+/// How cubes fall into pits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Falling {
+    /// a cube falls whole once any of its units is over a pit
+    #[default]
+    Whole,
+    /// only units over pits fall, and the rest are cut into pieces
+    Apart,
+}
+
+impl Tile {
+    /// The movement which the tile gives to cubes on it.
+    pub fn push(self) -> Option<Movement> {
+        match self {
+            Tile::Conveyor(movement) => Some(movement),
+            _ => None,
+        }
+    }
+
+    /// The kind which the tile gives to cubes moving onto it.
+    pub fn paint(self) -> Option<Kind> {
+        match self {
+            Tile::Paint(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// The direction which the tile turns cubes to.
+    pub fn rotate(self) -> Option<bool> {
+        match self {
+            Tile::Rotator { clockwise } => Some(clockwise),
+            _ => None,
+        }
+    }
+
+    /// The side of the blade which cuts cubes moving onto the tile.
+    pub fn cut(self) -> Option<Movement> {
+        match self {
+            Tile::Cutter(side) => Some(side),
+            _ => None,
+        }
+    }
+
+    /// The kind of cubes which pick up the key.
+    pub fn key(self) -> Option<Kind> {
+        match self {
+            Tile::Key(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// The kind of keys which open the lock.
+    pub fn lock(self) -> Option<Kind> {
+        match self {
+            Tile::Lock(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// When the wall on the tile is raised.
+    pub fn blink(self) -> Option<Blink> {
+        match self {
+            Tile::Blinker(blink) => Some(blink),
+            _ => None,
+        }
+    }
+
+    /// Whether units moving onto the tile fall.
+    pub fn pit(self) -> bool {
+        matches!(self, Tile::Pit)
+    }
+
+    /// The same tile with its direction changed. Rotators turn the other
+    /// way if `f` is a reflection.
+    pub fn turn(self, f: impl Fn(Movement) -> Movement) -> Self {
+        match self {
+            Tile::Conveyor(movement) => Tile::Conveyor(f(movement)),
+            Tile::Cutter(side) => Tile::Cutter(f(side)),
+            Tile::Rotator { clockwise } => {
+                let reflected = f(Movement::Up).clockwise() != f(Movement::Right);
+                Tile::Rotator {
+                    clockwise: clockwise != reflected,
+                }
+            }
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use super::{explore::StateGraph, rule::StateKey, seed::Seed};
+
+/// Difficulty is a breakdown of measurable properties of a level. Levels are
+/// comparable by `score`, the higher the harder.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    pub score: f32,
+    pub steps: usize,       // length of the shortest solution
+    pub states: usize,      // number of reachable states
+    pub branching: f32,     // average number of different next states
+    pub dead_ends: usize,   // number of states from which it is unsolvable
+    pub waits: usize,       // rounds without input in the shortest solution
+    pub merges: usize,      // cubes merged into the same kind in the solution
+    pub absorptions: usize, // cubes absorbed by another kind in the solution
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "score {:.2} (steps {}, states {}, branching {:.2}, dead ends {}, waits {}, merges {}, absorptions {})",
+            self.score,
+            self.steps,
+            self.states,
+            self.branching,
+            self.dead_ends,
+            self.waits,
+            self.merges,
+            self.absorptions,
+        )
+    }
+}
+
+/// Rate a level by exploring all of its states. It returns `None` if the
+/// level is unsolvable or has more than `limit` states.
+///
+/// The score is a heuristic for ordering levels:
+///
+/// `steps * (1 + dead_ends / states) + log2(states) + waits + merges + absorptions`
+pub fn estimate(seed: &Seed, limit: usize) -> Option<Difficulty> {
+    let graph = StateGraph::explore(seed, limit)?;
+    let path = graph.path()?;
+
+    let states = graph.keys.len();
+    let expanded = (0..states).filter(|&i| !graph.goal[i]).count();
+    let edges = (0..states)
+        .filter(|&i| !graph.goal[i])
+        .map(|i| graph.next[i].iter().filter(|&&j| j != i).count())
+        .sum::<usize>();
+    let branching = edges as f32 / expanded.max(1) as f32;
+    let dead_ends = graph.dead().into_iter().filter(|&dead| dead).count();
+
+    let steps = path.len() - 1;
+    let waits = path.iter().filter(|(_, input)| input.is_none()).count() - 1;
+    let (mut merges, mut absorptions) = (0, 0);
+    for pair in path.windows(2) {
+        let (m, a) = joins(&graph.keys[pair[0].0], &graph.keys[pair[1].0]);
+        merges += m;
+        absorptions += a;
+    }
+
+    let score = steps as f32 * (1. + dead_ends as f32 / states as f32)
+        + (states as f32).log2()
+        + (waits + merges + absorptions) as f32;
+
+    Some(Difficulty {
+        score,
+        steps,
+        states,
+        branching,
+        dead_ends,
+        waits,
+        merges,
+        absorptions,
+    })
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// internal
+
+/// Count cubes that joined others between two states as (merges, absorptions).
+fn joins(before: &StateKey, after: &StateKey) -> (usize, usize) {
+    let owner = before
+        .cubes()
+        .enumerate()
+        .flat_map(|(i, (kind, units))| units.iter().map(move |unit| (unit.0, (i, kind))))
+        .collect::<HashMap<_, _>>();
+
+    let (mut merges, mut absorptions) = (0, 0);
+    for (kind, units) in after.cubes() {
+        let mut sources = units
+            .iter()
+            .filter_map(|unit| owner.get(&unit.0))
+            .collect::<Vec<_>>();
+        sources.sort_unstable_by_key(|source| source.0);
+        sources.dedup_by_key(|source| source.0);
+
+        let same = sources.iter().filter(|source| source.1 == kind).count();
+        merges += same.saturating_sub(1);
+        absorptions += sources.len() - same;
+    }
+    (merges, absorptions)
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SeedBuilder;
+    use crate::cube::{Kind, Movement};
+    use crate::CubeCore;
+
+    #[test]
+    fn estimate_levels() {
+        /*****
+         *G G*
+         * xx*
+         *****/
+        let easy = SeedBuilder::new(3, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Green, [(2, 0)])
+            .target((1, 1))
+            .target((2, 1))
+            .build()
+            .unwrap();
+        let easy = estimate(&easy, 1000).unwrap();
+        assert_eq!(easy.steps, 2);
+        assert_eq!(easy.absorptions, 0);
+        assert_eq!(easy.waits, 0);
+
+        /******
+         *G   *
+         *  x *
+         *GxR *
+         ******/
+        let hard = SeedBuilder::new(4, 3)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Green, [(0, 2)])
+            .cube(Kind::Red, [(2, 2)])
+            .target((1, 2))
+            .target((2, 1))
+            .build()
+            .unwrap();
+        let hard = estimate(&hard, 1000).unwrap();
+        assert_eq!(hard.steps, 3);
+        assert_eq!(hard.absorptions, 1);
+        assert!(hard.dead_ends > 0);
+        assert!(hard.score > easy.score);
+
+        let level = SeedBuilder::new(2, 1);
+        let empty = level.clone().cube(Kind::Green, [(0, 0)]).build().unwrap();
+        assert_eq!(estimate(&empty, 1000).map(|o| o.steps), Some(0));
+        let unsolvable = level
+            .cube(Kind::Red, [(0, 0)])
+            .target((1, 0))
+            .build()
+            .unwrap();
+        assert_eq!(estimate(&unsolvable, 1000), None);
+    }
+
+    #[test]
+    fn count_joins() {
+        /*****
+         *GGW*
+         *****/
+        let seed = SeedBuilder::new(3, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Green, [(1, 0)])
+            .cube(Kind::White, [(2, 0)])
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let before = game.key();
+        game.commit(Some(Movement::Right)).for_each(drop);
+        assert_eq!(joins(&before, &game.key()), (1, 0));
+    }
+}
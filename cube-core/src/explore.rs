@@ -0,0 +1,347 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use super::{
+    cube::{Kind, Movement, Point, Tile},
+    glyph,
+    rule::StateKey,
+    seed::Seed,
+    solver::{solved, INPUTS},
+    state::CubeCore,
+};
+
+/// StateGraph contains every state reachable from the start of a level
+/// through `CubeCore::commit` with each of `INPUTS`. It is meant for small
+/// levels only.
+pub struct StateGraph {
+    pub(crate) width: i32,
+    pub(crate) height: i32,
+    pub(crate) dest: Vec<Point>,
+    pub(crate) tiles: Vec<(Point, Tile)>,
+    pub(crate) frozen: Vec<(usize, Kind, Point)>, // walls and bricks
+    pub(crate) keys: Vec<StateKey>,               // states in BFS order
+    pub(crate) next: Vec<Vec<usize>>,             // successors of each input
+    pub(crate) from: Vec<(usize, Option<Movement>)>, // the first way to reach
+    pub(crate) goal: Vec<bool>,
+}
+
+impl StateGraph {
+    /// Enumerate all states of a level. Solved states are not expanded as
+    /// the level ends there. It returns `None` if there are more than
+    /// `limit` states.
+    pub fn explore(seed: &Seed, limit: usize) -> Option<Self> {
+        Self::search(seed, limit, false)
+    }
+
+    /// Enumerate states in BFS order, and stop at the first solved state if
+    /// `first_goal` is set. Such a graph is partial, but its `path` is one
+    /// of the shortest.
+    pub(crate) fn search(seed: &Seed, limit: usize, first_goal: bool) -> Option<Self> {
+        let start = CubeCore::new(seed);
+        let locks = seed
+            .tiles
+            .iter()
+            .filter(|(_, tile)| tile.lock().is_some())
+            .map(|&(o, _)| o)
+            .collect::<Vec<_>>();
+        let mut graph = Self {
+            width: seed.size.width,
+            height: seed.size.height,
+            dest: seed.destnations.clone(),
+            tiles: seed.tiles.clone(),
+            // locks are frozen cells too, but drawn as tiles
+            frozen: start.frozen().filter(|o| !locks.contains(&o.2)).collect(),
+            keys: vec![start.key()],
+            next: vec![Vec::new()],
+            from: vec![(0, None)],
+            goal: vec![solved(&start)],
+        };
+
+        let mut index = HashMap::from([(start.key(), 0)]);
+        let mut queue = VecDeque::from([(0, start)]);
+        while let Some((current, state)) = queue.pop_front() {
+            if graph.goal[current] {
+                continue;
+            }
+
+            for input in INPUTS {
+                let mut next = state.clone();
+                next.commit(input).for_each(drop);
+                let key = next.key();
+                let target = match index.get(&key) {
+                    Some(&target) => target,
+                    None if graph.keys.len() >= limit => return None,
+                    None => {
+                        let target = graph.keys.len();
+                        index.insert(key.clone(), target);
+                        graph.keys.push(key);
+                        graph.next.push(Vec::new());
+                        graph.from.push((current, input));
+                        graph.goal.push(solved(&next));
+                        if first_goal && graph.goal[target] {
+                            return Some(graph);
+                        }
+                        queue.push_back((target, next));
+                        target
+                    }
+                };
+                graph.next[current].push(target);
+            }
+        }
+        Some(graph)
+    }
+
+    /// Number of states. The start state is always the first one.
+    pub fn states(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_goal(&self, state: usize) -> bool {
+        self.goal[state]
+    }
+
+    /// Mark states from which no goal is reachable.
+    pub fn dead(&self) -> Vec<bool> {
+        let mut prev = vec![Vec::new(); self.keys.len()];
+        for (i, next) in self.next.iter().enumerate() {
+            for &j in next {
+                prev[j].push(i);
+            }
+        }
+
+        let mut alive = self.goal.clone();
+        let mut queue = (0..self.keys.len())
+            .filter(|&i| alive[i])
+            .collect::<VecDeque<_>>();
+        while let Some(j) = queue.pop_front() {
+            for &i in prev[j].iter() {
+                if !alive[i] {
+                    alive[i] = true;
+                    queue.push_back(i);
+                }
+            }
+        }
+        alive.into_iter().map(|alive| !alive).collect()
+    }
+
+    /// The shortest path of (state, input) from the start to a goal.
+    pub(crate) fn path(&self) -> Option<Vec<(usize, Option<Movement>)>> {
+        // states are numbered in BFS order, so the first goal is the closest
+        let mut current = self.goal.iter().position(|&goal| goal)?;
+        let mut output = Vec::new();
+        while current != 0 {
+            let (parent, input) = self.from[current];
+            output.push((current, input));
+            current = parent;
+        }
+        output.push((0, None));
+        output.reverse();
+        Some(output)
+    }
+
+    /// Write the graph in DOT. Each state is drawn as its board, where tiles
+    /// are drawn as `<v^>` for conveyors, `&$%` for paints, `)` and `(` for
+    /// rotators, `/` for cutters, `.` for pits, `rgb` for keys, `@` for
+    /// locks, and `#` or `_` for raised or lowered blinkers. The start state
+    /// has a bold border, goals are green and dead states are red. Inputs
+    /// that change nothing are left out.
+    pub fn to_dot(&self) -> String {
+        let dead = self.dead();
+        let mut output = String::new();
+        let o = &mut output;
+
+        let _ = writeln!(o, "digraph states {{");
+        let _ = writeln!(
+            o,
+            "  node [shape=box, style=filled, fillcolor=white, fontname=monospace];"
+        );
+        for (i, key) in self.keys.iter().enumerate() {
+            let color = match (self.goal[i], dead[i]) {
+                (true, _) => "palegreen",
+                (_, true) => "lightpink",
+                _ => "white",
+            };
+            let width = if i == 0 { 3 } else { 1 };
+            let _ = writeln!(
+                o,
+                "  s{} [label=\"{}\", fillcolor={}, penwidth={}];",
+                i,
+                self.draw(key),
+                color,
+                width
+            );
+        }
+
+        for (i, next) in self.next.iter().enumerate() {
+            let mut edges = Vec::<(usize, String)>::with_capacity(next.len());
+            for (&j, input) in next.iter().zip(INPUTS) {
+                if i == j {
+                    continue;
+                }
+                let name = match input {
+                    None => '_',
+                    Some(Movement::Left) => 'L',
+                    Some(Movement::Down) => 'D',
+                    Some(Movement::Up) => 'U',
+                    Some(Movement::Right) => 'R',
+                };
+                match edges.iter_mut().find(|edge| edge.0 == j) {
+                    Some(edge) => edge.1.push(name),
+                    None => edges.push((j, name.into())),
+                }
+            }
+            for (j, label) in edges {
+                let _ = writeln!(o, "  s{} -> s{} [label=\"{}\"];", i, j, label);
+            }
+        }
+        let _ = writeln!(o, "}}");
+        output
+    }
+
+    fn draw(&self, key: &StateKey) -> String {
+        let (w, h) = (self.width.max(0) as usize, self.height.max(0) as usize);
+        let mut board = vec![' '; w * h];
+        let mut put = |o: Point, c: char| {
+            if 0 <= o.x && o.x < self.width && 0 <= o.y && o.y < self.height {
+                board[o.y as usize * w + o.x as usize] = c;
+            }
+        };
+        for &(o, tile) in self.tiles.iter() {
+            let c = match tile {
+                Tile::Conveyor(Movement::Left) => '<',
+                Tile::Conveyor(Movement::Down) => 'v',
+                Tile::Conveyor(Movement::Up) => '^',
+                Tile::Conveyor(Movement::Right) => '>',
+                Tile::Paint(kind) => glyph::paint(kind).unwrap_or(' '),
+                Tile::Rotator { clockwise: true } => ')',
+                Tile::Rotator { clockwise: false } => '(',
+                Tile::Cutter(_) => '/',
+                Tile::Pit => glyph::PIT,
+                _ if key.spent().contains(&o) => continue,
+                Tile::Key(kind) => glyph::letter(kind).to_ascii_lowercase(),
+                Tile::Lock(_) => '@',
+                Tile::Blinker(blink) if blink.raised(key.round()) => '#',
+                Tile::Blinker(_) => '_',
+            };
+            put(o, c);
+        }
+        for &o in self.dest.iter() {
+            put(o, 'x');
+        }
+        for &(index, kind, o) in self.frozen.iter() {
+            if key.broken().binary_search(&index).is_err() {
+                put(o, glyph::letter(kind));
+            }
+        }
+        for (kind, units) in key.cubes() {
+            let c = glyph::letter(kind);
+            for &(_, o) in units {
+                put(o, c);
+            }
+        }
+
+        // left-justified lines in DOT
+        let mut output = String::with_capacity((w + 2) * h);
+        for row in board.chunks(w.max(1)) {
+            output.extend(row);
+            output.push_str("\\l");
+        }
+        output
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{Rect, SeedBuilder};
+
+    #[test]
+    fn export() {
+        /****
+         *G *
+         * x*
+         ****/
+
+        let seed = SeedBuilder::new(2, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .target((1, 1))
+            .build()
+            .unwrap();
+
+        let graph = StateGraph::explore(&seed, 100).unwrap();
+        assert_eq!(graph.states(), 4);
+        assert_eq!(graph.dead(), vec![false; 4]);
+        assert_eq!((0..4).filter(|&i| graph.is_goal(i)).count(), 1);
+        assert!(StateGraph::explore(&seed, 3).is_none());
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph states {"));
+        assert!(dot.contains("s0 [label=\"G \\l x\\l\", fillcolor=white, penwidth=3];"));
+        assert!(dot.contains("s0 -> s1 [label=\"D\"];"));
+        assert!(dot.contains("s0 -> s2 [label=\"R\"];"));
+        assert!(dot.contains("fillcolor=palegreen"));
+        assert!(!dot.contains("s0 -> s0"));
+    }
+
+    #[test]
+    fn frozen() {
+        use crate::seed::Command;
+
+        /******
+         *GOxW*
+         *WKWW*
+         ******/
+
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Left), 1)],
+        };
+        let seed = SeedBuilder::new(4, 2)
+            .wall(Rect::new(0, 1, 1, 1))
+            .wall(Rect::new(2, 1, 2, 1))
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Bomb, [(1, 0)])
+            .cube(Kind::Brick, [(1, 1)])
+            .cube(Kind::White, [(3, 0)])
+            .script([(3, 0)], command)
+            .target((2, 0))
+            .build()
+            .unwrap();
+
+        // walls and bricks are drawn until bricks are broken.
+        let dot = StateGraph::explore(&seed, 100).unwrap().to_dot();
+        assert!(dot.contains("s0 [label=\"GOxW\\lWKWW\\l\""));
+        assert!(dot.contains("[label=\"GCxW\\lW WW\\l\""));
+    }
+
+    #[test]
+    fn tiles() {
+        use crate::cube::Blink;
+
+        /*******
+         *G>g@ x*
+         *******/
+
+        let blink = Blink {
+            period: 2,
+            phase: 0,
+        };
+        let seed = SeedBuilder::new(6, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .tile((1, 0), Tile::Conveyor(Movement::Right))
+            .tile((2, 0), Tile::Key(Kind::Green))
+            .tile((3, 0), Tile::Lock(Kind::Green))
+            .tile((4, 0), Tile::Blinker(blink))
+            .target((5, 0))
+            .build()
+            .unwrap();
+
+        // keys and locks are gone once picked up, and blinkers go up and down.
+        let dot = StateGraph::explore(&seed, 100).unwrap().to_dot();
+        assert!(dot.contains("s0 [label=\"G>g@#x\\l\""));
+        assert!(dot.contains("[label=\" >G _x\\l\""));
+    }
+}
@@ -0,0 +1,221 @@
+use std::ops::RangeInclusive;
+
+use super::{
+    cube::{Falling, Kind, Movement, Point},
+    seed::{Command, Cube, Info, Seed, Size},
+    solver::solve,
+    validate::validate,
+};
+
+#[derive(Clone, Debug)]
+pub struct Parameters {
+    /// size of levels
+    pub size: Size,
+    /// number of single unit cubes of each kind
+    pub cubes: Vec<(Kind, usize)>,
+    /// probability of each point to be a wall
+    pub walls: f32,
+    /// number of destinations
+    pub destinations: usize,
+    /// allow cubes to move by themselves
+    pub scripted: bool,
+    /// length of the shortest solution
+    pub steps: RangeInclusive<usize>,
+    /// maximum states searched for each candidate
+    pub limit: usize,
+    /// maximum candidates tried for each level
+    pub attempts: usize,
+}
+
+/// Generator makes an endless stream of solvable levels. The same `seed`
+/// always gives the same levels. The stream ends if none of the candidates
+/// fits in `attempts` tries.
+pub struct Generator {
+    parameters: Parameters,
+    random: Random,
+    count: usize,
+}
+
+impl Generator {
+    pub fn new(parameters: Parameters, seed: u64) -> Self {
+        Self {
+            parameters,
+            random: Random(seed),
+            count: 0,
+        }
+    }
+
+    fn candidate(&mut self) -> Seed {
+        let Parameters {
+            size,
+            cubes,
+            walls,
+            destinations,
+            scripted,
+            ..
+        } = &self.parameters;
+
+        let mut points = (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| Point::new(x, y)))
+            .collect::<Vec<_>>();
+        self.random.shuffle(&mut points);
+        let mut points = points.into_iter();
+
+        let mut output = Vec::new();
+        for &(kind, number) in cubes.iter() {
+            for o in points.by_ref().take(number) {
+                let command = match kind != Kind::Green && *scripted && self.random.chance(0.5) {
+                    true => Some(self.random.command()),
+                    false => None,
+                };
+                output.push(Cube {
+                    kind,
+                    body: vec![o],
+                    command,
+                    mirror: None,
+                    pursuit: None,
+                });
+            }
+        }
+
+        let destnations = points.by_ref().take(*destinations).collect();
+        for o in points {
+            if self.random.chance(*walls) {
+                output.push(Cube {
+                    kind: Kind::White,
+                    body: vec![o],
+                    command: None,
+                    mirror: None,
+                    pursuit: None,
+                });
+            }
+        }
+
+        Seed {
+            info: Info {
+                title: format!("Generated {}", self.count),
+                author: "generator".into(),
+            },
+            size: size.clone(),
+            cubes: output,
+            destnations,
+            tiles: Vec::new(),
+            gravity: None,
+            falling: Falling::Whole,
+        }
+    }
+}
+
+impl Iterator for Generator {
+    type Item = Seed;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for _ in 0..self.parameters.attempts {
+            let seed = self.candidate();
+            if seed.check().is_err() || !validate(&seed).is_empty() {
+                continue;
+            }
+
+            let limit = self.parameters.limit;
+            match solve(&seed, limit) {
+                Some(solution) if self.parameters.steps.contains(&solution.len()) => {
+                    self.count += 1;
+                    return Some(seed);
+                }
+                _ => continue,
+            }
+        }
+        None
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Random
+
+/// SplitMix64, which is good enough for making levels.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, limit: usize) -> usize {
+        (self.next() % limit.max(1) as u64) as usize
+    }
+
+    fn chance(&mut self, probability: f32) -> bool {
+        ((self.next() >> 40) as f32 / (1u64 << 24) as f32) < probability
+    }
+
+    fn shuffle<T>(&mut self, list: &mut [T]) {
+        for i in (1..list.len()).rev() {
+            list.swap(i, self.below(i + 1));
+        }
+    }
+
+    fn command(&mut self) -> Command {
+        let length = 1 + self.below(4);
+        let movements = (0..length)
+            .map(|_| {
+                let movement = match self.below(Movement::ALL.len() + 1) {
+                    0 => None,
+                    i => Some(Movement::ALL[i - 1]),
+                };
+                (movement, 1 + self.below(2))
+            })
+            .collect();
+        Command {
+            is_loop: true,
+            movements,
+        }
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solver::solved, CubeCore};
+
+    #[test]
+    fn generate() {
+        let parameters = Parameters {
+            size: Size {
+                width: 5,
+                height: 4,
+            },
+            cubes: vec![(Kind::Green, 1), (Kind::Red, 1), (Kind::Blue, 1)],
+            walls: 0.2,
+            destinations: 2,
+            scripted: true,
+            steps: 3..=8,
+            limit: 20000,
+            attempts: 200,
+        };
+
+        let levels = Generator::new(parameters.clone(), 42)
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(levels.len(), 3);
+        for seed in levels.iter() {
+            let solution = solve(seed, parameters.limit).unwrap();
+            assert!(parameters.steps.contains(&solution.len()));
+
+            let mut game = CubeCore::new(seed);
+            for input in solution {
+                game.commit(input).for_each(drop);
+            }
+            assert!(solved(&game));
+        }
+
+        let again = Generator::new(parameters, 42).take(3).collect::<Vec<_>>();
+        assert_eq!(levels, again);
+    }
+}
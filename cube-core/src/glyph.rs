@@ -0,0 +1,143 @@
+use super::{
+    cube::{Kind, Point},
+    rule::DisjointSet,
+};
+
+/// The letter of a cube in level drawings.
+pub(crate) const fn letter(kind: Kind) -> char {
+    match kind {
+        Kind::White => 'W',
+        Kind::Green => 'G',
+        Kind::Blue => 'B',
+        Kind::Red => 'R',
+        Kind::Crate => 'C',
+        Kind::Brick => 'K',
+        Kind::Bomb => 'O',
+    }
+}
+
+/// The glyph of a pit in level drawings.
+pub(crate) const PIT: char = '.';
+
+/// The letter of a paint tile in level drawings, if it has one.
+pub(crate) const fn paint(kind: Kind) -> Option<char> {
+    match kind {
+        Kind::Green => Some('&'),
+        Kind::Blue => Some('$'),
+        Kind::Red => Some('%'),
+        _ => None,
+    }
+}
+
+/// Draw cubes and targets in the glyph alphabet of level files, so that
+/// reading the drawing gives the same cubes. Each unit is drawn as either a
+/// letter starting a new cube, `-` linked to the left, `|` linked to the
+/// upper or `/` joining the upper and the left.
+///
+/// It returns `None` if cubes overlap, are outside, cover targets, or have
+/// bodies which are not connected.
+pub(crate) fn draw<'a>(
+    width: i32,
+    height: i32,
+    cubes: impl Iterator<Item = (Kind, &'a [Point])>,
+    targets: &[Point],
+) -> Option<Vec<Vec<char>>> {
+    let (w, h) = (width.max(0) as usize, height.max(0) as usize);
+    let inside = |o: &Point| 0 <= o.x && o.x < width && 0 <= o.y && o.y < height;
+    let at = |o: &Point| o.y as usize * w + o.x as usize;
+
+    let mut kinds = Vec::new();
+    let mut owner = vec![None; w * h];
+    for (i, (kind, body)) in cubes.enumerate() {
+        kinds.push(kind);
+        for o in body {
+            match inside(o) {
+                true if owner[at(o)].is_none() => owner[at(o)] = Some(i),
+                _ => return None,
+            }
+        }
+    }
+
+    let mut output = vec![vec![' '; w]; h];
+    for o in targets {
+        match inside(o) && owner[at(o)].is_none() {
+            true => output[o.y as usize][o.x as usize] = 'x',
+            false => return None,
+        }
+    }
+
+    // simulate how a reader groups units
+    let mut connection = DisjointSet::new(w * h);
+    let mut group = vec![None; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            let Some(cube) = owner[y * w + x] else {
+                continue;
+            };
+            let mut near = |ok: bool, i: usize| match ok && owner[i] == Some(cube) {
+                true => group[i].map(|g| connection.find(g)),
+                false => None,
+            };
+            let left = near(x > 0, (y * w + x).wrapping_sub(1));
+            let upper = near(y > 0, (y * w + x).wrapping_sub(w));
+
+            let (c, g) = match (left, upper) {
+                (Some(l), Some(u)) if l != u => {
+                    connection.join(u, l);
+                    ('/', l)
+                }
+                (Some(l), _) => ('-', l),
+                (None, Some(u)) => ('|', u),
+                (None, None) => (letter(kinds[cube]), y * w + x),
+            };
+            output[y][x] = c;
+            group[y * w + x] = Some(g);
+        }
+    }
+
+    // each cube must be read as exactly one cube
+    let mut roots = vec![None; kinds.len()];
+    for i in 0..w * h {
+        if let (Some(cube), Some(g)) = (owner[i], group[i]) {
+            let g = connection.find(g);
+            match roots[cube] {
+                None => roots[cube] = Some(g),
+                Some(r) if r != g => return None,
+                _ => {}
+            }
+        }
+    }
+    Some(output)
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drawing() {
+        let points = |list: &[(i32, i32)]| list.iter().map(Point::from).collect::<Vec<_>>();
+        let u = points(&[(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)]);
+        let l = points(&[(2, 2), (1, 3), (2, 3)]);
+        let w = points(&[(0, 3)]);
+        let cubes = [
+            (Kind::Green, &u[..]),
+            (Kind::Red, &l[..]),
+            (Kind::White, &w[..]),
+        ];
+
+        let output = draw(3, 4, cubes.iter().cloned(), &[Point::new(1, 0)]).unwrap();
+        let output = output.iter().map(String::from_iter).collect::<Vec<_>>();
+        assert_eq!(output, ["GxG", "|-/", "  R", "WR/"]);
+
+        let apart = points(&[(0, 0), (2, 0)]);
+        let cubes = [(Kind::Green, &apart[..])];
+        assert_eq!(draw(3, 1, cubes.iter().cloned(), &[]), None);
+        let cubes = [(Kind::Green, &u[..])];
+        assert_eq!(draw(3, 2, cubes.iter().cloned(), &[Point::new(0, 0)]), None);
+        assert_eq!(draw(2, 2, cubes.iter().cloned(), &[]), None);
+    }
+}
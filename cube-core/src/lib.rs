@@ -1,14 +1,24 @@
+mod glyph;
 mod rule;
 
+pub mod board;
+pub mod builder;
 pub mod cube;
+pub mod difficulty;
+pub mod explore;
+pub mod generate;
+pub mod minimize;
 pub mod seed;
+pub mod solver;
 pub mod state;
+pub mod validate;
 
-pub use self::rule::{Diff, Unit};
+pub use self::rule::{Cause, Diff, Explanation, Unit};
 pub use self::state::*;
 
 #[cfg(test)]
 mod tests {
+    use crate::board::Board;
     use crate::cube::*;
     use crate::seed::*;
     use crate::*;
@@ -22,130 +32,744 @@ mod tests {
          *****/
 
         // STEP 00
-        let seed = Seed {
-            info: Info {
-                title: "test".into(),
-                author: "test".into(),
-            },
-            size: Size {
-                width: 3,
-                height: 3,
-            },
-            cubes: vec![
-                Cube {
-                    kind: Kind::Green,
-                    body: vec![Point::new(0, 0)],
-                    command: None,
-                },
-                Cube {
-                    kind: Kind::Blue,
-                    body: vec![Point::new(0, 1)],
-                    command: None,
-                },
-                Cube {
-                    kind: Kind::White,
-                    body: vec![Point::new(1, 0)],
-                    command: None,
-                },
-            ],
-            destnations: vec![Point::new(1, 0), Point::new(0, 2)],
-        };
-        let mut game = CubeCore::new(&seed);
-        let stat = [
-            Unit {
-                id: 0,
-                kind: Kind::Green,
-                position: Point::new(0, 0),
-                movement: None,
-                constraint: Constraint::Free,
-                neighborhood: Neighborhood::new(),
-            },
-            Unit {
-                id: 1,
-                kind: Kind::Blue,
-                position: Point::new(0, 1),
-                movement: None,
-                constraint: Constraint::Free,
-                neighborhood: Neighborhood::new(),
-            },
-            Unit {
-                id: 2,
-                kind: Kind::White,
-                position: Point::new(1, 0),
-                movement: None,
-                constraint: Constraint::Free,
-                neighborhood: Neighborhood::new(),
-            },
+        let seed = builder::SeedBuilder::new(3, 3)
+            .info("test", "test")
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Blue, [(0, 1)])
+            .cube(Kind::White, [(1, 0)])
+            .target((1, 0))
+            .target((0, 2))
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let board = |text: &str| text.parse::<Board>().unwrap();
+        let neighborhood = |game: &CubeCore| {
+            game.iter()
+                .map(|unit| (unit.id, unit.neighborhood))
+                .collect::<Vec<_>>()
+        };
+        // walls are linked to the outside of the board.
+        let outside = [Adjacence::LEFT_TOP, Adjacence::TOP, Adjacence::RIGHT_TOP];
+        let wall = (2, Neighborhood::from(outside.into_iter()));
+        let apart = [(0, Neighborhood::new()), (1, Neighborhood::new()), wall];
+        let linked = [
+            (0, Neighborhood::from([Adjacence::BOTTOM].into_iter())),
+            (1, Neighborhood::from([Adjacence::TOP].into_iter())),
+            wall,
         ];
-        assert_eq!(game.iter().collect::<Vec<_>>(), stat);
+        let stat = board(
+            "
+            *************
+            *GW *.. *.. *
+            *B  *.  *.  *
+            *x  *   *   *
+            *************
+            ",
+        );
+        assert_eq!(Board::from(&game), stat);
+        assert_eq!(game.to_string(), stat.to_string());
+        assert_eq!(neighborhood(&game), apart);
         assert_eq!(game.goals().filter(|(_, o)| *o).count(), 1);
 
         // STEP 01
-        let diff = [
-            Diff {
-                id: 0,
-                movement: Some(Some(Movement::Right)),
-                constraint: Some(Constraint::Stop),
-                neighborhood: Some(Neighborhood::from([Adjacence::BOTTOM].into_iter())),
-                ..Default::default()
-            },
-            Diff {
-                id: 1,
-                kind: Some(Kind::Green),
-                movement: Some(Some(Movement::Right)),
-                constraint: Some(Constraint::Stop),
-                neighborhood: Some(Neighborhood::from([Adjacence::TOP].into_iter())),
-                ..Default::default()
-            },
-        ];
-        assert_eq!(game.commit(Some(Movement::Right)).collect::<Vec<_>>(), diff);
+        let _ = game.commit(Some(Movement::Right)).count();
+        let stat = board(
+            "
+            *************
+            *GW *>. *S. *
+            *|  *>  *S  *
+            *x  *   *   *
+            *************
+            ",
+        );
+        assert_eq!(Board::from(&game), stat);
+        assert_eq!(game.to_string(), stat.to_string());
+        assert_eq!(neighborhood(&game), linked);
         assert_eq!(game.goals().filter(|(_, o)| *o).count(), 1);
 
         // STEP 02
-        let diff = [
-            Diff {
-                id: 0,
-                movement: Some(Some(Movement::Down)),
-                position: Some(Point::new(0, 1)),
-                constraint: Some(Constraint::Free),
-                ..Default::default()
-            },
-            Diff {
-                id: 1,
-                movement: Some(Some(Movement::Down)),
-                position: Some(Point::new(0, 2)),
-                constraint: Some(Constraint::Free),
-                ..Default::default()
-            },
-        ];
-        assert_eq!(game.commit(Some(Movement::Down)).collect::<Vec<_>>(), diff);
-        let stat = [
-            Unit {
-                id: 0,
-                kind: Kind::Green,
-                position: Point::new(0, 1),
-                movement: Some(Movement::Down),
-                constraint: Constraint::Free,
-                neighborhood: Neighborhood::from([Adjacence::BOTTOM].into_iter()),
-            },
-            Unit {
-                id: 1,
-                kind: Kind::Green,
-                position: Point::new(0, 2),
-                movement: Some(Movement::Down),
-                constraint: Constraint::Free,
-                neighborhood: Neighborhood::from([Adjacence::TOP].into_iter()),
-            },
-            Unit {
-                id: 2,
-                kind: Kind::White,
-                position: Point::new(1, 0),
-                movement: None,
-                constraint: Constraint::Free,
-                neighborhood: Neighborhood::new(),
-            },
-        ];
-        assert_eq!(game.iter().collect::<Vec<_>>(), stat);
+        let _ = game.commit(Some(Movement::Down)).count();
+        let stat = board(
+            "
+            *************
+            * W * . * . *
+            *G  *v  *.  *
+            *|  *v  *.  *
+            *************
+            ",
+        );
+        assert_eq!(Board::from(&game), stat);
+        assert_eq!(game.to_string(), stat.to_string());
+        assert_eq!(neighborhood(&game), linked);
         assert_eq!(game.goals().filter(|(_, o)| *o).count(), 2);
     }
+
+    #[test]
+    fn try_new() {
+        /****
+         *GR*
+         * x*
+         ****/
+
+        let seed = builder::SeedBuilder::new(2, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Red, [(1, 0)])
+            .target((1, 1))
+            .build()
+            .unwrap();
+        assert!(CubeCore::try_new(&seed).is_ok());
+
+        let mut other = seed.clone();
+        other.size.height = -1;
+        assert!(matches!(
+            CubeCore::try_new(&other),
+            Err(SeedError::BadSize(Size { height: -1, .. }))
+        ));
+
+        let mut other = seed.clone();
+        other.cubes[1].body[0] = Point::new(0, 0);
+        let error = CubeCore::try_new(&other).err();
+        assert_eq!(error, Some(SeedError::Overlapped(Point::new(0, 0))));
+
+        let mut other = seed.clone();
+        other.cubes[1].body[0] = Point::new(2, 0);
+        let error = CubeCore::try_new(&other).err();
+        assert_eq!(error, Some(SeedError::Outside(Point::new(2, 0))));
+
+        let mut other = seed.clone();
+        other.destnations[0] = Point::new(1, 2);
+        let error = CubeCore::try_new(&other).err();
+        assert_eq!(error, Some(SeedError::Outside(Point::new(1, 2))));
+
+        let mut other = seed;
+        other.cubes[1].command = Some(Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Down), 1), (None, 0)],
+        });
+        let error = CubeCore::try_new(&other).err();
+        let cube = Point::new(1, 0);
+        assert_eq!(error, Some(SeedError::ZeroStep { cube, step: 1 }));
+    }
+
+    #[test]
+    fn preview() {
+        /*****
+         *GB *
+         *R  *
+         *****/
+
+        let seed = builder::SeedBuilder::new(3, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Blue, [(1, 0)])
+            .cube(Kind::Red, [(0, 1)])
+            .target((2, 1))
+            .build()
+            .unwrap();
+
+        let mut game = CubeCore::new(&seed);
+        let units = game.iter().collect::<Vec<_>>();
+        for movement in [None, Some(Movement::Right), Some(Movement::Down)] {
+            let preview = game.preview(movement);
+            assert_eq!(game.iter().collect::<Vec<_>>(), units);
+
+            let mut other = CubeCore::new(&seed);
+            assert_eq!(other.commit(movement).collect::<Vec<_>>(), preview);
+        }
+
+        let mut other = CubeCore::new(&seed);
+        let _ = other.commit(Some(Movement::Right)).count();
+        let _ = game.commit(Some(Movement::Down)).count();
+        let _ = game.preview(Some(Movement::Left));
+        let _ = game.remake(Some(Movement::Right)).count();
+        assert_eq!(
+            game.iter().collect::<Vec<_>>(),
+            other.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn explain() {
+        /*****
+         *GGW*
+         *  R*
+         *****/
+
+        let seed = builder::SeedBuilder::new(3, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Green, [(1, 0)])
+            .cube(Kind::White, [(2, 0)])
+            .cube(Kind::Red, [(2, 1)])
+            .build()
+            .unwrap();
+
+        let mut game = CubeCore::new(&seed);
+        assert_eq!(game.explain(0), None);
+
+        let _ = game.commit(Some(Movement::Right)).count();
+        let wall = Explanation {
+            constraint: Constraint::Stop,
+            cause: Cause::Wall(Point::new(2, 0)),
+            chain: vec![],
+        };
+        assert_eq!(game.explain(1), Some(wall.clone()));
+        assert_eq!(
+            game.explain(0),
+            Some(Explanation {
+                chain: vec![1],
+                ..wall
+            })
+        );
+        assert_eq!(game.explain(2), None);
+    }
+
+    #[test]
+    fn gravity() {
+        /*****
+         *G C*
+         *   *
+         *WWW*
+         *****/
+
+        let seed = builder::SeedBuilder::new(3, 3)
+            .wall(builder::Rect::new(0, 2, 3, 1))
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Crate, [(2, 0)])
+            .gravity(Movement::Down)
+            .build()
+            .unwrap();
+
+        let mut game = CubeCore::new(&seed);
+        let position = |game: &CubeCore, id| game.iter().find(|o| o.id == id).unwrap().position;
+        let (green, crate_) = (0, 1);
+
+        // greens still follow the input, and others fall.
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        let fall = diffs.iter().find(|o| o.id == crate_).unwrap();
+        assert_eq!(fall.movement, Some(Some(Movement::Down)));
+        assert_eq!(position(&game, green), Point::new(1, 0));
+        assert_eq!(position(&game, crate_), Point::new(2, 1));
+
+        // greens fall without input, and walls hold cubes.
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game, green), Point::new(1, 1));
+        assert_eq!(position(&game, crate_), Point::new(2, 1));
+        let unit = game.iter().find(|o| o.id == crate_).unwrap();
+        assert_eq!(unit.constraint, Constraint::Stop);
+
+        // gravity moves nothing without it.
+        let mut other = seed.clone();
+        other.gravity = None;
+        let mut game = CubeCore::new(&other);
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game, crate_), Point::new(2, 0));
+
+        // grounded crates are still pushed sideways.
+        let seed = builder::SeedBuilder::new(3, 2)
+            .wall(builder::Rect::new(0, 1, 3, 1))
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Crate, [(1, 0)])
+            .gravity(Movement::Down)
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let _ = game.commit(Some(Movement::Right)).count();
+        assert_eq!(position(&game, green), Point::new(1, 0));
+        assert_eq!(position(&game, crate_), Point::new(2, 0));
+    }
+
+    #[test]
+    fn conveyor() {
+        use builder::SeedBuilder;
+
+        let position = |game: &CubeCore| game.iter().find(|o| o.id == 0).unwrap().position;
+        let right = Tile::Conveyor(Movement::Right);
+        let left = Tile::Conveyor(Movement::Left);
+
+        // conveyors carry cubes before gravity, but inputs win.
+        let seed = SeedBuilder::new(3, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .tile((0, 0), right)
+            .tile((1, 0), right)
+            .gravity(Movement::Down)
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game), Point::new(1, 0));
+        let _ = game.commit(Some(Movement::Left)).count();
+        assert_eq!(position(&game), Point::new(0, 0));
+
+        // scripts win too.
+        let command = Command {
+            is_loop: false,
+            movements: vec![(Some(Movement::Down), 1)],
+        };
+        let seed = SeedBuilder::new(3, 2)
+            .cube(Kind::Red, [(0, 0)])
+            .tile((0, 0), right)
+            .script([(0, 0)], command)
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game), Point::new(0, 1));
+
+        // units on conveyors vote, and the others abstain.
+        let cube = SeedBuilder::new(4, 1).cube(Kind::Red, [(0, 0), (1, 0)]);
+        let mut game = CubeCore::new(&cube.clone().tile((0, 0), right).build().unwrap());
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game), Point::new(1, 0));
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game), Point::new(1, 0));
+
+        let mixed = cube.tile((0, 0), right).tile((1, 0), left);
+        let mut game = CubeCore::new(&mixed.build().unwrap());
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game), Point::new(0, 0));
+
+        // a tie leaves the cube to gravity.
+        let seed = SeedBuilder::new(4, 2)
+            .cube(Kind::Red, [(0, 0), (1, 0)])
+            .tile((0, 0), right)
+            .tile((1, 0), left)
+            .gravity(Movement::Down)
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let _ = game.commit(None).count();
+        assert_eq!(position(&game), Point::new(0, 1));
+    }
+
+    #[test]
+    fn paint() {
+        use builder::SeedBuilder;
+
+        // cubes are recolored after moving onto paints.
+        let seed = SeedBuilder::new(3, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .tile((1, 0), Tile::Paint(Kind::Blue))
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        assert_eq!(diffs[0].kind, Some(Kind::Blue));
+        assert_eq!(diffs[0].position, Some(Point::new(1, 0)));
+        let _ = game.commit(Some(Movement::Right)).count();
+        assert_eq!(game.iter().next().unwrap().position, Point::new(1, 0));
+
+        // paints of different kinds disagree.
+        let command = Command {
+            is_loop: false,
+            movements: vec![(Some(Movement::Right), 1)],
+        };
+        let seed = SeedBuilder::new(3, 1)
+            .cube(Kind::Red, [(0, 0), (1, 0)])
+            .tile((1, 0), Tile::Paint(Kind::Blue))
+            .tile((2, 0), Tile::Paint(Kind::Green))
+            .script([(0, 0)], command)
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(None).collect::<Vec<_>>();
+        assert!(diffs
+            .iter()
+            .all(|o| o.kind.is_none() && o.position.is_some()));
+
+        // pushed crates keep their kind.
+        let seed = SeedBuilder::new(3, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Crate, [(1, 0)])
+            .tile((2, 0), Tile::Paint(Kind::Red))
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        let pushed = diffs.iter().find(|o| o.id == 1).unwrap();
+        assert_eq!(pushed.position, Some(Point::new(2, 0)));
+        assert_eq!(pushed.kind, None);
+    }
+
+    #[test]
+    fn rotator() {
+        use builder::{Rect, SeedBuilder};
+
+        /*****
+         *   *
+         *RR *
+         *****/
+
+        let command = Command {
+            is_loop: false,
+            movements: vec![(Some(Movement::Right), 1)],
+        };
+        let builder = SeedBuilder::new(3, 2)
+            .cube(Kind::Red, [(0, 1), (1, 1)])
+            .tile((2, 1), Tile::Rotator { clockwise: true })
+            .script([(0, 1)], command);
+        let body = |game: &CubeCore| {
+            let board = Board::from(game);
+            let mut reds = board.cubes.into_iter().filter(|o| o.kind == Kind::Red);
+            let red = reds.next().unwrap();
+            assert!(reds.next().is_none());
+            red.body
+        };
+
+        // cubes turn around the unit on the rotator once they come to rest
+        // on it, and only once.
+        let mut game = CubeCore::new(&builder.clone().build().unwrap());
+        let _ = game.commit(None).count();
+        assert_eq!(body(&game), [Point::new(1, 1), Point::new(2, 1)]);
+        let diffs = game.commit(None).collect::<Vec<_>>();
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs
+            .iter()
+            .all(|o| o.pivot == Some((Point::new(2, 1), true))));
+        assert_eq!(body(&game), [Point::new(2, 0), Point::new(2, 1)]);
+        let _ = game.commit(None).count();
+        assert_eq!(body(&game), [Point::new(2, 0), Point::new(2, 1)]);
+
+        // but not if the way is blocked.
+        let seed = builder.wall(Rect::new(1, 0, 1, 1)).build().unwrap();
+        let mut game = CubeCore::new(&seed);
+        let _ = game.commit(None).count();
+        let diffs = game.commit(None).collect::<Vec<_>>();
+        assert!(diffs.iter().all(|o| o.pivot.is_none()));
+        assert_eq!(body(&game), [Point::new(1, 1), Point::new(2, 1)]);
+
+        // nor if they only pass over it.
+        let command = Command {
+            is_loop: false,
+            movements: vec![(Some(Movement::Right), 3)],
+        };
+        let seed = SeedBuilder::new(5, 2)
+            .cube(Kind::Red, [(0, 1), (1, 1)])
+            .tile((2, 1), Tile::Rotator { clockwise: true })
+            .script([(0, 1)], command)
+            .build()
+            .unwrap();
+        let mut game = CubeCore::new(&seed);
+        for _ in 0..4 {
+            let _ = game.commit(None).count();
+        }
+        assert_eq!(body(&game), [Point::new(3, 1), Point::new(4, 1)]);
+    }
+
+    #[test]
+    fn cutter() {
+        use builder::SeedBuilder;
+
+        /*****
+         *RR *
+         *   *
+         *****/
+
+        let command = Command {
+            is_loop: false,
+            movements: vec![(Some(Movement::Down), 1), (Some(Movement::Right), 1)],
+        };
+        let seed = SeedBuilder::new(3, 2)
+            .cube(Kind::Red, [(0, 0), (1, 0)])
+            .tile((0, 1), Tile::Cutter(Movement::Right))
+            .script([(0, 0)], command)
+            .build()
+            .unwrap();
+        let bodies = |game: &CubeCore| {
+            let board = Board::from(game);
+            board.cubes.into_iter().map(|o| o.body).collect::<Vec<_>>()
+        };
+
+        // cubes are cut along the line of the blade.
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(None).collect::<Vec<_>>();
+        assert!(diffs.iter().all(|o| o.neighborhood.is_some()));
+        assert_eq!(bodies(&game), [[Point::new(0, 1)], [Point::new(1, 1)]]);
+
+        // and pieces go on with their copies of the command.
+        let _ = game.commit(None).count();
+        assert_eq!(bodies(&game), [[Point::new(1, 1)], [Point::new(2, 1)]]);
+    }
+
+    #[test]
+    fn cutter_team() {
+        use builder::SeedBuilder;
+
+        /*****
+         *RR *
+         *   *
+         *   *
+         *****/
+
+        let command = |first, last| Command {
+            is_loop: false,
+            movements: vec![(Some(first), 1), (Some(Movement::Down), 2), (Some(last), 1)],
+        };
+        let seed = SeedBuilder::new(3, 3)
+            .cube(Kind::Red, [(0, 0)])
+            .cube(Kind::Red, [(1, 0)])
+            .tile((0, 2), Tile::Cutter(Movement::Right))
+            .script([(0, 0)], command(Movement::Right, Movement::Up))
+            .script([(1, 0)], command(Movement::Down, Movement::Right))
+            .build()
+            .unwrap();
+        let bodies = |game: &CubeCore| {
+            let board = Board::from(game);
+            board.cubes.into_iter().map(|o| o.body).collect::<Vec<_>>()
+        };
+
+        // cubes merge while one moves into the other, and they are cut
+        // apart on the blade. then each piece goes on with its own command.
+        let mut game = CubeCore::new(&seed);
+        let _ = game.commit(None).count();
+        assert_eq!(bodies(&game), [[Point::new(0, 0), Point::new(1, 0)]]);
+        for _ in 0..3 {
+            let _ = game.commit(None).count();
+        }
+        assert_eq!(bodies(&game), [[Point::new(0, 1)], [Point::new(2, 2)]]);
+    }
+
+    #[test]
+    fn bomb() {
+        use builder::SeedBuilder;
+
+        /*******
+         *GO KK*
+         *******/
+
+        let seed = SeedBuilder::new(5, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Bomb, [(1, 0)])
+            .cube(Kind::Brick, [(3, 0), (4, 0)])
+            .build()
+            .unwrap();
+        let bricks = |game: &CubeCore| {
+            let units = game.iter().filter(|unit| unit.kind == Kind::Brick);
+            units.map(|unit| unit.position).collect::<Vec<_>>()
+        };
+
+        // bombs are pushed like crates, and moving ones never go off.
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        assert!(diffs.iter().all(|o| !o.removed));
+        assert_eq!(bricks(&game), [Point::new(3, 0), Point::new(4, 0)]);
+
+        // a bomb only stopped by bricks never goes off.
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        assert!(diffs.iter().all(|o| !o.removed));
+        let bomb = game.explain(1).map(|o| o.constraint);
+        assert_eq!(bomb, Some(Constraint::Stop));
+        assert_eq!(bricks(&game), [Point::new(3, 0), Point::new(4, 0)]);
+
+        /******
+         *GO W*
+         * K  *
+         ******/
+
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Left), 1)],
+        };
+        let seed = SeedBuilder::new(4, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Bomb, [(1, 0)])
+            .cube(Kind::White, [(3, 0)])
+            .script([(3, 0)], command)
+            .cube(Kind::Brick, [(1, 1)])
+            .build()
+            .unwrap();
+
+        // a bomb knocking on a cube breaks bricks next to it, and leaves a
+        // crate.
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        let removed = diffs.iter().filter(|o| o.removed).map(|o| o.id);
+        assert_eq!(removed.collect::<Vec<_>>(), [3]);
+        let bomb = game.explain(1).map(|o| o.constraint);
+        assert_eq!(bomb, Some(Constraint::Slap));
+        assert!(diffs.iter().any(|o| o.kind == Some(Kind::Crate)));
+        assert_eq!(bricks(&game), []);
+    }
+
+    #[test]
+    fn pit() {
+        use builder::SeedBuilder;
+
+        /*****
+         *GGG*
+         * . *
+         *****/
+
+        let builder = SeedBuilder::new(3, 2)
+            .cube(Kind::Green, [(0, 0), (1, 0), (2, 0)])
+            .tile((1, 1), Tile::Pit);
+        let bodies = |game: &CubeCore| {
+            let board = Board::from(game);
+            board.cubes.into_iter().map(|o| o.body).collect::<Vec<_>>()
+        };
+
+        // a cube falls whole by default, and units are removed on the pit.
+        let mut game = CubeCore::new(&builder.clone().build().unwrap());
+        let diffs = game.commit(Some(Movement::Down)).collect::<Vec<_>>();
+        assert_eq!(diffs.iter().filter(|o| o.removed).count(), 3);
+        assert!(diffs.iter().all(|o| o.position.is_some()));
+        assert_eq!(game.iter().count(), 0);
+        assert_eq!(game.commit(Some(Movement::Up)).count(), 0);
+
+        // or only units on pits fall, and the rest are cut into pieces.
+        let seed = builder.falling(Falling::Apart).build().unwrap();
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Down)).collect::<Vec<_>>();
+        let removed = diffs.iter().filter(|o| o.removed).map(|o| o.id);
+        assert_eq!(removed.collect::<Vec<_>>(), [1]);
+        assert_eq!(bodies(&game), [[Point::new(0, 1)], [Point::new(2, 1)]]);
+
+        // and pieces move on without the fallen unit.
+        let _ = game.commit(Some(Movement::Up)).count();
+        assert_eq!(bodies(&game), [[Point::new(0, 0)], [Point::new(2, 0)]]);
+    }
+
+    #[test]
+    fn restore() {
+        use builder::SeedBuilder;
+
+        /*****
+         *G. *
+         *K  *
+         *****/
+
+        let seed = SeedBuilder::new(3, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Brick, [(0, 1)])
+            .tile((1, 0), Tile::Pit)
+            .build()
+            .unwrap();
+
+        // a fallen unit comes back when the round is made again.
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        assert!(diffs.iter().any(|o| o.id == 0 && o.removed));
+        let diffs = game.remake(None).collect::<Vec<_>>();
+        let green = diffs.iter().find(|o| o.id == 0);
+        assert_eq!(green.and_then(|o| o.position), Some(Point::new(0, 0)));
+        assert!(green.is_some_and(|o| !o.removed));
+        assert_eq!(game.iter().filter(|o| o.kind == Kind::Green).count(), 1);
+
+        /******
+         *GO W*
+         * K  *
+         ******/
+
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Left), 1)],
+        };
+        let seed = SeedBuilder::new(4, 2)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::Bomb, [(1, 0)])
+            .cube(Kind::White, [(3, 0)])
+            .script([(3, 0)], command)
+            .cube(Kind::Brick, [(1, 1)])
+            .build()
+            .unwrap();
+
+        // and so does a broken brick.
+        let mut game = CubeCore::new(&seed);
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        assert!(diffs.iter().any(|o| o.id == 3 && o.removed));
+        let diffs = game.remake(None).collect::<Vec<_>>();
+        let brick = diffs.iter().find(|o| o.id == 3);
+        assert_eq!(brick.and_then(|o| o.position), Some(Point::new(1, 1)));
+        assert_eq!(brick.and_then(|o| o.kind), Some(Kind::Brick));
+        assert!(brick.is_some_and(|o| !o.removed));
+    }
+
+    #[test]
+    fn lock() {
+        use builder::SeedBuilder;
+
+        /*******
+         *Gk L *
+         *******/
+
+        let builder = SeedBuilder::new(5, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .tile((3, 0), Tile::Lock(Kind::Green));
+        let green = |game: &CubeCore| {
+            let mut units = game.iter().filter(|unit| unit.kind == Kind::Green);
+            units.next().map(|unit| unit.position)
+        };
+
+        // a key of another kind opens nothing.
+        let seed = builder.clone().tile((1, 0), Tile::Key(Kind::Red));
+        let mut game = CubeCore::new(&seed.build().unwrap());
+        for _ in 0..4 {
+            let _ = game.commit(Some(Movement::Right)).count();
+        }
+        assert_eq!(green(&game), Some(Point::new(2, 0)));
+        assert_eq!(game.tiles().count(), 2);
+
+        // the key is picked up by covering it, and the lock opens for good.
+        let seed = builder.tile((1, 0), Tile::Key(Kind::Green));
+        let mut game = CubeCore::new(&seed.build().unwrap());
+        let lock = |game: &CubeCore| game.iter().any(|unit| unit.position == Point::new(3, 0));
+        assert!(lock(&game));
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        let removed = diffs.iter().filter(|o| o.removed).map(|o| o.id);
+        assert_eq!(removed.collect::<Vec<_>>(), [1]);
+        assert_eq!(game.tiles().count(), 0);
+        for _ in 0..3 {
+            let _ = game.commit(Some(Movement::Right)).count();
+        }
+        assert_eq!(green(&game), Some(Point::new(4, 0)));
+        assert!(!lock(&game));
+    }
+
+    #[test]
+    fn blink() {
+        use builder::SeedBuilder;
+
+        /*****
+         *G^  *
+         *****/
+
+        let blink = |phase| Tile::Blinker(Blink { period: 2, phase });
+        let builder = SeedBuilder::new(3, 1).cube(Kind::Green, [(0, 0)]);
+        let green = |game: &CubeCore| game.iter().find(|o| o.id == 0).unwrap().position;
+        let wall = |game: &CubeCore| game.iter().find(|o| o.id == 1).unwrap().hidden;
+
+        // cubes never move onto a blinker raised in the coming round.
+        let seed = builder.clone().tile((1, 0), blink(1)).build().unwrap();
+        let mut game = CubeCore::new(&seed);
+        assert!(wall(&game));
+        let _ = game.commit(Some(Movement::Right)).count();
+        assert_eq!(green(&game), Point::new(0, 0));
+        assert_eq!(
+            game.explain(0).map(|o| o.cause),
+            Some(Cause::Wall(Point::new(1, 0)))
+        );
+
+        // the blinker lowers, and a cube covering it holds it lowered.
+        let seed = builder.tile((1, 0), blink(0)).build().unwrap();
+        let mut game = CubeCore::new(&seed);
+        assert!(!wall(&game));
+        let diffs = game.commit(Some(Movement::Right)).collect::<Vec<_>>();
+        let hidden = diffs.iter().find(|o| o.id == 1).and_then(|o| o.hidden);
+        assert_eq!(hidden, Some(true));
+        assert_eq!(green(&game), Point::new(1, 0));
+        assert!(game.commit(None).all(|o| o.id != 1));
+        assert!(wall(&game));
+
+        // keys differ only by the round in the cycle.
+        let key = game.key();
+        let _ = game.commit(None).count();
+        assert_ne!(game.key(), key);
+        let _ = game.commit(None).count();
+        assert_eq!(game.key(), key);
+
+        // it is raised again once the cube leaves, which blocks it back.
+        let _ = game.commit(Some(Movement::Right)).count();
+        let diffs = game.commit(Some(Movement::Left)).collect::<Vec<_>>();
+        let hidden = diffs.iter().find(|o| o.id == 1).and_then(|o| o.hidden);
+        assert_eq!(hidden, Some(false));
+        assert_eq!(green(&game), Point::new(2, 0));
+    }
 }
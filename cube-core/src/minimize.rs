@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+
+use super::{
+    cube::{Falling, Movement, Point},
+    seed::{disconnected, Seed},
+    state::CubeCore,
+};
+
+/// Shrink a level and its inputs while `holds` is still true, which makes a
+/// small reproducer of a rule bug. It removes inputs, cubes, units, targets,
+/// behaviors of cubes, steps of commands and empty borders one by one until
+/// nothing more can be removed.
+///
+/// Bodies of cubes are kept connected, so the result can be written by
+/// `Seed::to_toml` if the original one can. If `holds` is false at the
+/// beginning, the originals are returned as they are.
+pub fn minimize<F>(
+    seed: &Seed,
+    inputs: &[Option<Movement>],
+    mut holds: F,
+) -> (Seed, Vec<Option<Movement>>)
+where
+    F: FnMut(&Seed, &[Option<Movement>]) -> bool,
+{
+    let mut seed = seed.clone();
+    let mut inputs = inputs.to_vec();
+    if !holds(&seed, &inputs) {
+        return (seed, inputs);
+    }
+
+    let mut reduced = true;
+    while reduced {
+        reduced = false;
+
+        // inputs, from the last one
+        for i in (0..inputs.len()).rev() {
+            let mut other = inputs.clone();
+            other.remove(i);
+            if holds(&seed, &other) {
+                inputs = other;
+                reduced = true;
+            }
+        }
+
+        for candidate in candidates(&seed) {
+            if holds(&candidate, &inputs) {
+                seed = candidate;
+                reduced = true;
+                break;
+            }
+        }
+    }
+    (seed, inputs)
+}
+
+/// Check whether two units are at the same point after any round.
+pub fn overlapped(seed: &Seed, inputs: &[Option<Movement>]) -> bool {
+    let mut game = CubeCore::new(seed);
+    inputs.iter().any(|&input| {
+        game.commit(input).for_each(drop);
+        let mut visit = HashSet::new();
+        !game.iter().all(|unit| visit.insert(unit.position))
+    })
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// internal
+
+/// All seeds with exactly one thing removed, bigger things first.
+fn candidates(seed: &Seed) -> Vec<Seed> {
+    let mut output = Vec::new();
+
+    for i in 0..seed.cubes.len() {
+        let mut other = seed.clone();
+        other.cubes.remove(i);
+        output.push(other);
+    }
+    for i in 0..seed.destnations.len() {
+        let mut other = seed.clone();
+        other.destnations.remove(i);
+        output.push(other);
+    }
+    for i in 0..seed.tiles.len() {
+        let mut other = seed.clone();
+        other.tiles.remove(i);
+        output.push(other);
+    }
+    for (i, cube) in seed.cubes.iter().enumerate() {
+        for j in (0..cube.body.len()).filter(|_| cube.body.len() > 1) {
+            let mut other = seed.clone();
+            other.cubes[i].body.remove(j);
+            if disconnected(&other.cubes[i].body).is_none() {
+                output.push(other);
+            }
+        }
+    }
+    for (i, cube) in seed.cubes.iter().enumerate() {
+        if cube.command.is_some() {
+            let mut other = seed.clone();
+            other.cubes[i].command = None;
+            output.push(other);
+        }
+        if cube.mirror.is_some() {
+            let mut other = seed.clone();
+            other.cubes[i].mirror = None;
+            output.push(other);
+        }
+        if cube.pursuit.is_some() {
+            let mut other = seed.clone();
+            other.cubes[i].pursuit = None;
+            output.push(other);
+        }
+    }
+    if seed.gravity.is_some() {
+        let mut other = seed.clone();
+        other.gravity = None;
+        output.push(other);
+    }
+    if seed.falling != Falling::Whole {
+        let mut other = seed.clone();
+        other.falling = Falling::Whole;
+        output.push(other);
+    }
+    for (i, cube) in seed.cubes.iter().enumerate() {
+        let Some(command) = &cube.command else {
+            continue;
+        };
+        for (j, step) in command.movements.iter().enumerate() {
+            let mut other = seed.clone();
+            let movements = &mut other.cubes[i].command.as_mut().unwrap().movements;
+            match step.1 > 1 {
+                true => movements[j].1 -= 1,
+                false if movements.len() > 1 => {
+                    movements.remove(j);
+                }
+                false => continue,
+            }
+            output.push(other);
+        }
+    }
+
+    // borders, which move everything when the top or the left is removed
+    let (w, h) = (seed.size.width, seed.size.height);
+    let empty = |inside: &dyn Fn(&Point) -> bool| {
+        let points = seed.cubes.iter().flat_map(|cube| cube.body.iter());
+        let tiles = seed.tiles.iter().map(|o| &o.0);
+        !points
+            .chain(seed.destnations.iter())
+            .chain(tiles)
+            .any(inside)
+    };
+    let borders: [(bool, bool, Point); 4] = [
+        (h > 1 && empty(&|o| o.y == h - 1), false, Point::new(0, 0)),
+        (w > 1 && empty(&|o| o.x == w - 1), true, Point::new(0, 0)),
+        (h > 1 && empty(&|o| o.y == 0), false, Point::new(0, -1)),
+        (w > 1 && empty(&|o| o.x == 0), true, Point::new(-1, 0)),
+    ];
+    for (empty, horizontal, offset) in borders {
+        if empty {
+            let mut other = seed.clone();
+            match horizontal {
+                true => other.size.width -= 1,
+                false => other.size.height -= 1,
+            }
+            for cube in other.cubes.iter_mut() {
+                cube.body.iter_mut().for_each(|o| *o += offset);
+            }
+            other.destnations.iter_mut().for_each(|o| *o += offset);
+            other.tiles.iter_mut().for_each(|o| o.0 += offset);
+            output.push(other);
+        }
+    }
+
+    output
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{Rect, SeedBuilder};
+    use crate::cube::{Constraint, Kind};
+    use crate::seed::Command;
+
+    #[test]
+    fn reduce() {
+        /********
+         *  W   *
+         *GG  R *
+         *G   Bx*
+         * x WW *
+         ********/
+
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Left), 2), (None, 1)],
+        };
+        let seed = SeedBuilder::new(6, 4)
+            .info("test", "test")
+            .cube(Kind::White, [(2, 0)])
+            .cube(Kind::Green, [(0, 1), (1, 1), (0, 2)])
+            .cube(Kind::Red, [(4, 1)])
+            .script([(4, 1)], command)
+            .cube(Kind::Blue, [(4, 2)])
+            .wall(Rect::new(3, 3, 2, 1))
+            .target((5, 2))
+            .target((1, 3))
+            .build()
+            .unwrap();
+        let inputs = [
+            Some(Movement::Right),
+            None,
+            Some(Movement::Up),
+            Some(Movement::Right),
+            Some(Movement::Down),
+        ];
+
+        // some green unit is stopped by something in the last round
+        let stopped = |seed: &Seed, inputs: &[Option<Movement>]| {
+            let mut game = CubeCore::new(seed);
+            for &input in inputs {
+                game.commit(input).for_each(drop);
+            }
+            let stopped = game
+                .iter()
+                .any(|unit| unit.kind == Kind::Green && unit.constraint == Constraint::Stop);
+            stopped
+        };
+        assert!(stopped(&seed, &inputs));
+
+        let (seed, inputs) = minimize(&seed, &inputs, stopped);
+        assert!(stopped(&seed, &inputs));
+        assert!(inputs.len() < 5);
+        assert!(seed.cubes.len() < 5);
+        assert!(seed.to_toml().is_some());
+        for i in 0..inputs.len() {
+            let mut other = inputs.clone();
+            other.remove(i);
+            assert!(!stopped(&seed, &other));
+        }
+        for other in candidates(&seed) {
+            assert!(!stopped(&other, &inputs));
+        }
+
+        // a lonely green cube stopped by borders
+        let lonely = SeedBuilder::new(seed.size.width, seed.size.height)
+            .info("test", "test")
+            .cube(Kind::Green, [(2, 0)])
+            .build()
+            .unwrap();
+        let (lonely, inputs) = minimize(&lonely, &[None, Some(Movement::Up)], stopped);
+        assert_eq!(inputs, [Some(Movement::Up)]);
+        assert_eq!(
+            lonely.to_toml().unwrap(),
+            "[map]\nraw = '''\nG\n'''\n\n[info]\nauthor = \"test\"\ntitle = \"test\"\n"
+        );
+
+        // nothing changes if it never holds
+        let (other, _) = minimize(&seed, &inputs, |_, _| false);
+        assert_eq!(other, seed);
+        assert!(!overlapped(&seed, &inputs));
+    }
+
+    #[test]
+    fn tiles() {
+        use crate::cube::Tile;
+
+        /******
+         *    *
+         * G  *
+         *    *
+         ******/
+
+        // the green cube is carried by the conveyor under it
+        let carried = |seed: &Seed, inputs: &[Option<Movement>]| {
+            let mut game = CubeCore::new(seed);
+            inputs.iter().for_each(|&o| game.commit(o).for_each(drop));
+            let carried = game.iter().any(|unit| {
+                unit.kind == Kind::Green
+                    && unit.movement == Some(Movement::Right)
+                    && unit.constraint == Constraint::Free
+            });
+            carried
+        };
+        let seed = SeedBuilder::new(4, 3)
+            .cube(Kind::Green, [(1, 1)])
+            .tile((1, 1), Tile::Conveyor(Movement::Right))
+            .tile((0, 2), Tile::Conveyor(Movement::Left))
+            .build()
+            .unwrap();
+
+        let (seed, _) = minimize(&seed, &[None], carried);
+        assert_eq!((seed.size.width, seed.size.height), (2, 1));
+        assert_eq!(seed.cubes[0].body, [Point::new(0, 0)]);
+        assert_eq!(
+            seed.tiles,
+            [(Point::new(0, 0), Tile::Conveyor(Movement::Right))]
+        );
+    }
+}
@@ -1,14 +1,21 @@
+mod blinking;
 mod collection;
 mod extension;
 mod frozen;
 mod lookup;
 mod output;
 mod snapshot;
+mod trace;
 
+#[cfg(test)]
+mod conformance;
+
+pub(crate) use blinking::*;
 pub(crate) use collection::*;
 pub(crate) use extension::*;
 pub(crate) use frozen::*;
 pub(crate) use lookup::*;
 pub(crate) use snapshot::*;
+pub(crate) use trace::*;
 
-pub use output::{Diff, Unit};
+pub use output::{Cause, Diff, Explanation, Unit};
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::cube::{Blink, Point};
+
+/// Blinking keeps walls which are raised and lowered by rounds, besides the
+/// frozen ones. Nothing changes here, as the round is kept by collections.
+#[derive(Clone, Debug)]
+pub struct Blinking {
+    walls: Vec<(Point, Blink)>,
+    lookup: HashMap<Point, usize>, // indexes of walls by their points
+    cycle: usize,                  // rounds after which all walls repeat
+}
+
+impl Blinking {
+    pub fn new<I>(it: I) -> Self
+    where
+        I: Iterator<Item = (Point, Blink)>,
+    {
+        fn gcd(a: usize, b: usize) -> usize {
+            match b {
+                0 => a,
+                _ => gcd(b, a % b),
+            }
+        }
+
+        let walls = it.collect::<Vec<_>>();
+        let lookup = walls
+            .iter()
+            .enumerate()
+            .map(|(index, &(o, _))| (o, index))
+            .collect();
+        let cycle = walls.iter().fold(1, |cycle, (_, blink)| {
+            let period = blink.period.max(1) as usize;
+            cycle / gcd(cycle, period) * period
+        });
+
+        Self {
+            walls,
+            lookup,
+            cycle,
+        }
+    }
+
+    /// Whether a wall at the point is raised in the round, even if a cube
+    /// covers it.
+    pub fn raised(&self, point: Point, round: usize) -> bool {
+        let index = self.lookup.get(&point);
+        index.is_some_and(|&index| self.walls[index].1.raised(round))
+    }
+
+    /// Rounds after which all walls are raised and lowered again in the
+    /// same way.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Points of walls, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.walls.iter().map(|o| o.0)
+    }
+}
@@ -3,30 +3,51 @@ use std::{
     sync::Arc,
 };
 
-use super::{output, CollisionExtension, Digraph, DisjointSet, Frozen, HashSetCollision, Snapshot};
-use crate::cube::{Adjacence, Agreement, Constraint, Kind, Motion, Movement, Neighborhood, Point};
+use super::{
+    output::{self, Cause},
+    Blinking, CollisionExtension, Digraph, DisjointSet, Frozen, HashSetCollision, Snapshot, Trace,
+};
+use crate::cube::{
+    Adjacence, Agreement, Constraint, Falling, Kind, Motion, Movement, Neighborhood, Point, Sight,
+    Tile,
+};
 
 /////////////////////////////////////////////////////////////////////////////
 // export
 
 #[derive(Clone, Debug)]
 pub struct Collection {
-    cube: Vec<Cube>,   // cubes (sets of units)
-    area: Arc<Frozen>, // background and obstacles
+    cube: Vec<Cube>,                  // cubes (sets of units)
+    area: Arc<Frozen>,                // background and obstacles
+    blinking: Arc<Blinking>,          // walls raised and lowered by rounds
+    round: usize,                     // rounds committed
+    gravity: Option<Movement>,        // the pull on cubes without movements
+    falling: Falling,                 // how cubes fall into pits
+    fallen: Vec<output::Unit>,        // units removed by pits, as last seen
+    spent: Vec<Point>,                // picked keys and opened locks
+    tiles: Arc<HashMap<Point, Tile>>, // floor cells
 }
 
 impl Collection {
-    pub fn new<'a, I>(width: usize, height: usize, it: I) -> Self
+    pub fn new<'a, I, T>(
+        width: usize,
+        height: usize,
+        gravity: Option<Movement>,
+        falling: Falling,
+        tiles: T,
+        it: I,
+    ) -> Self
     where
         I: Iterator<Item = (Kind, &'a [Point], Motion)> + 'a,
+        T: Iterator<Item = (Point, Tile)>,
     {
         let mut index = 0;
         let mut count = 0;
         let mut cubes = Vec::new();
         let mut other = Vec::new();
         for (kind, points, motion) in it {
-            if kind == Kind::White && motion.is_stopped() {
-                other.push(points);
+            if kind == Kind::Brick || (kind == Kind::White && motion.is_stopped()) {
+                other.push((kind, points));
                 continue;
             }
 
@@ -52,6 +73,8 @@ impl Collection {
                 balanced: false,
                 movement: None,
                 constraint: Constraint::Free,
+                arrived: false,
+                turned: None,
             };
 
             cubes.push(cube);
@@ -59,9 +82,28 @@ impl Collection {
             index += 1;
         }
 
+        // locks are walls until they are opened.
+        let tiles = tiles.collect::<Vec<_>>();
+        let locks = tiles
+            .iter()
+            .filter_map(|&(o, tile)| tile.lock().map(|_| o))
+            .collect::<Vec<_>>();
+
+        let blinkers = tiles
+            .iter()
+            .filter_map(|&(o, tile)| Some((o, tile.blink()?)));
+        let blinking = Blinking::new(blinkers);
+
         Self {
             cube: cubes,
-            area: Arc::new(Frozen::new(width, height, other.into_iter())),
+            area: Arc::new(Frozen::new(width, height, other.into_iter(), &locks)),
+            blinking: Arc::new(blinking),
+            round: 0,
+            gravity,
+            falling,
+            fallen: Vec::new(),
+            spent: Vec::new(),
+            tiles: Arc::new(tiles.into_iter().collect()),
         }
     }
 
@@ -73,6 +115,40 @@ impl Collection {
         self.area.height()
     }
 
+    /// Frozen cells of (index, kind, position) which are not removed yet.
+    pub(crate) fn frozen(&self) -> impl Iterator<Item = (usize, Kind, Point)> + '_ {
+        self.area
+            .iter()
+            .map(|(index, cell)| (index, cell.0, cell.1))
+    }
+
+    /// Whether the key or the lock at the point is gone.
+    pub fn spent(&self, point: Point) -> bool {
+        self.spent.contains(&point)
+    }
+
+    pub fn key(&self) -> StateKey {
+        let mut cubes = self
+            .cube
+            .iter()
+            .filter(|cube| cube.alive())
+            .map(|cube| {
+                let mut units = cube
+                    .units
+                    .iter()
+                    .map(|unit| (unit.index, unit.position))
+                    .collect::<Vec<_>>();
+                units.sort_unstable_by_key(|unit| unit.0);
+                (cube.kind, units, cube.motion.clone(), cube.arrived)
+            })
+            .collect::<Vec<_>>();
+        cubes.sort_unstable_by_key(|cube| cube.1.first().map(|unit| unit.0));
+        let mut spent = self.spent.clone();
+        spent.sort_unstable_by_key(|o| (o.y, o.x));
+        let round = self.round % self.blinking.cycle();
+        StateKey(cubes, self.area.broken().to_vec(), spent, round)
+    }
+
     pub fn snapshot(&self) -> Snapshot {
         let default = output::Unit {
             id: 0,
@@ -81,9 +157,17 @@ impl Collection {
             movement: None,
             constraint: Constraint::Free,
             neighborhood: Neighborhood::new(),
+            hidden: false,
         };
         let size = self.cube.iter().map(|cube| cube.units.len()).sum::<usize>();
+        let size = size + self.fallen.len();
         let mut output = vec![default; size];
+        let mut removed = vec![false; size];
+        let mut pivots = vec![None; size];
+        for unit in self.fallen.iter() {
+            output[unit.id] = unit.clone();
+            removed[unit.id] = true;
+        }
         for cube in self.cube.iter() {
             for unit in cube.units.iter() {
                 output[unit.index] = output::Unit {
@@ -93,45 +177,138 @@ impl Collection {
                     movement: cube.movement,
                     constraint: cube.constraint,
                     neighborhood: unit.neighborhood,
+                    hidden: false,
                 };
+                pivots[unit.index] = cube.turned;
             }
         }
-        Snapshot::new(output, Arc::clone(&self.area))
+
+        // a cube covering a blinker holds it lowered.
+        let covered = self
+            .cube
+            .iter()
+            .flat_map(|cube| cube.units.iter().map(|unit| unit.position))
+            .collect::<HashSet<_>>();
+        let offset = size + self.area.capacity();
+        let blinking = self
+            .blinking
+            .iter()
+            .enumerate()
+            .map(|(index, o)| output::Unit {
+                id: index + offset,
+                kind: Kind::White,
+                position: o,
+                movement: None,
+                constraint: Constraint::Free,
+                neighborhood: Neighborhood::new(),
+                hidden: !self.blinking.raised(o, self.round) || covered.contains(&o),
+            })
+            .collect();
+        Snapshot::new(output, removed, pivots, Arc::clone(&self.area), blinking)
     }
 
-    pub fn commit(&mut self, movement: Option<Movement>) {
+    pub fn commit(&mut self, movement: Option<Movement>, mut trace: Option<&mut Trace>) {
+        // cubes never move onto blinkers raised in the coming round.
+        self.round += 1;
+
         // clean and update movements.
-        self.update_cube_status();
+        self.update_cube_status(movement);
         self.update_cube_movement(movement);
 
+        // let moving cubes push crates in front of them.
+        let pushes = self.process_pushed_cubes();
+
+        // pull the others, so that pushes win over gravity.
+        self.update_cube_gravity();
+
         // try to connect cubes directly.
         self.process_imbalanced_cubes();
 
         // find blocked cubes and mark them with Constraint::Stop, and
         // also find out the movement dependencies between them.
-        let successors = self.process_blocked_cubes();
+        let successors = self.process_blocked_cubes(&pushes, trace.as_deref_mut());
 
         // find conflicts and mark them with Constraint::Lock.
-        let competed = self.process_conflicted_cubes(&successors);
+        let competed = self.process_conflicted_cubes(&successors, trace.as_deref_mut());
 
         // solve competed positions and mark them with Constraint::Slap.
-        self.process_competed_cubes(&successors, competed);
+        self.process_competed_cubes(&successors, competed, trace.as_deref_mut());
 
         // update cubes with next positions.
         self.update_cube_positions();
 
+        // split cubes moved onto cutters into pieces.
+        self.process_cut_cubes();
+
+        // recolor cubes moved onto paint tiles, which absorb and link with
+        // their new kinds in the next round.
+        self.process_painted_cubes();
+
+        // turn cubes which came to rest on rotators if nothing is in the way.
+        self.process_rotated_cubes();
+
+        // remove units moved onto pits, and cut the rest if they fall apart.
+        self.process_fallen_cubes();
+
+        // set off bombs knocking on cubes next to bricks, which leave crates.
+        self.process_exploded_cubes();
+
+        // pick up keys covered by cubes of their kinds, which open locks.
+        self.process_unlocked_cubes();
+
+        // keep reasons consistent with the final constraints.
+        if let Some(trace) = trace {
+            for cube in self.cube.iter().filter(|cube| cube.alive()) {
+                trace.settle(cube.ids(), cube.constraint);
+            }
+        }
+
         // do some cleaning.
         self.retain_alive_cube();
     }
 
-    fn update_cube_status(&mut self) {
+    fn update_cube_status(&mut self, movement: Option<Movement>) {
+        const CHASED: Kind = Kind::Green;
+        let targets = self
+            .cube
+            .iter()
+            .filter(|cube| cube.kind == CHASED)
+            .flat_map(|cube| cube.units.iter().map(|unit| unit.position))
+            .collect::<Vec<_>>();
+
+        let tiles = &self.tiles;
+        let mut body = Vec::new();
         for cube in self.cube.iter_mut() {
+            body.clear();
+            body.extend(cube.units.iter().map(|unit| unit.position));
+            let sight = Sight {
+                input: movement,
+                body: &body,
+                targets: &targets,
+            };
+
+            // units on conveyors vote, and the others abstain.
+            let conveyor =
+                Agreement::vote(body.iter().filter_map(|o| tiles.get(o)?.push()).map(Some));
+
             cube.balanced = false;
-            cube.movement = cube.motion.next().unwrap_or_default();
+            cube.turned = None;
+            cube.movement = cube
+                .motion
+                .next(&sight)
+                .unwrap_or_default()
+                .or(conveyor.flatten());
             cube.constraint = Constraint::Free;
         }
     }
 
+    fn update_cube_gravity(&mut self) {
+        let gravity = self.gravity;
+        for cube in self.cube.iter_mut().filter(|cube| cube.movement.is_none()) {
+            cube.movement = gravity;
+        }
+    }
+
     fn update_cube_movement(&mut self, movement: Option<Movement>) {
         const CONTROLED: Kind = Kind::Green;
         if let Some(movement) = movement {
@@ -141,6 +318,62 @@ impl Collection {
         }
     }
 
+    /// Give crates the movements of cubes pushing them, and return pairs of
+    /// (pusher, pushed) for the crates which move.
+    fn process_pushed_cubes(&mut self) -> Vec<(usize, usize)> {
+        let number_of_cubes = self.cube.len();
+        let mut movements = self
+            .cube
+            .iter()
+            .map(|cube| cube.movement)
+            .collect::<Vec<_>>();
+
+        // push crates wave by wave, so that rows of crates are pushed through.
+        let territory = Territory::new(self.cube.iter().filter(|cube| cube.alive()));
+        let mut pushers = (0..number_of_cubes).collect::<Vec<_>>();
+        let mut visit = vec![false; number_of_cubes];
+        let mut pairs = Vec::new();
+        while !pushers.is_empty() {
+            let mut pushed = HashMap::<usize, (Option<Movement>, Vec<usize>)>::new();
+            for &index in pushers.iter() {
+                let Some(movement) = movements[index] else {
+                    continue;
+                };
+                let cube = Moving {
+                    cube: &self.cube[index],
+                    movement,
+                };
+                for other in territory.neighbors_in_front(&cube) {
+                    if other.kind.pushable() && movements[other.index].is_none() {
+                        // a crate pushed in different directions stays
+                        let entry = pushed
+                            .entry(other.index)
+                            .or_insert((Some(movement), vec![]));
+                        entry.0 = entry.0.filter(|&o| o == movement);
+                        entry.1.push(index);
+                    }
+                }
+            }
+
+            pushers.clear();
+            for (index, (movement, from)) in pushed {
+                if !visit[index] {
+                    visit[index] = true;
+                    movements[index] = movement;
+                    if movement.is_some() {
+                        pushers.push(index);
+                        pairs.extend(from.into_iter().map(|pusher| (pusher, index)));
+                    }
+                }
+            }
+        }
+
+        for (cube, movement) in self.cube.iter_mut().zip(movements) {
+            cube.movement = movement;
+        }
+        pairs
+    }
+
     fn update_cube_positions(&mut self) {
         for cube in self.cube.iter_mut() {
             if cube.constraint == Constraint::Free {
@@ -154,6 +387,204 @@ impl Collection {
         }
     }
 
+    fn process_cut_cubes(&mut self) {
+        let tiles = &self.tiles;
+        let mut pieces = Vec::new();
+        for cube in self.cube.iter_mut().filter(|cube| cube.moved()) {
+            let blades = cube
+                .units
+                .iter()
+                .filter_map(|unit| Some((unit.position, tiles.get(&unit.position)?.cut()?)))
+                .collect::<Vec<_>>();
+            if blades.is_empty() {
+                continue;
+            }
+
+            // a link is cut if it crosses the line of any blade.
+            let cut = |a: Point, b: Point| {
+                blades.iter().any(|&(o, side)| {
+                    let d = Point::from(side);
+                    let axis = |p: Point| p.x * d.x + p.y * d.y;
+                    (b - a == d || a - b == d) && axis(a).min(axis(b)) == axis(o).min(axis(o + d))
+                })
+            };
+
+            pieces.extend(cube.split(|a, b| !cut(a, b)));
+        }
+
+        for mut piece in pieces {
+            piece.index = self.cube.len();
+            self.cube.push(piece);
+        }
+    }
+
+    fn process_painted_cubes(&mut self) {
+        let tiles = &self.tiles;
+        let painted = |cube: &&mut Cube| cube.moved() && cube.kind.paintable();
+        for cube in self.cube.iter_mut().filter(painted) {
+            // a cube on paints of different kinds keeps its own.
+            let mut kinds = cube
+                .units
+                .iter()
+                .filter_map(|unit| tiles.get(&unit.position)?.paint());
+            if let Some(kind) = kinds.next() {
+                if kinds.all(|other| other == kind) {
+                    cube.kind = kind;
+                }
+            }
+        }
+    }
+
+    fn process_rotated_cubes(&mut self) {
+        let tiles = &self.tiles;
+        let area = &self.area;
+        let raised = |o: Point| self.blinking.raised(o, self.round);
+        let mut occupied = self
+            .cube
+            .iter()
+            .flat_map(|cube| cube.units.iter().map(|unit| unit.position))
+            .collect::<HashSet<_>>();
+
+        for cube in self.cube.iter_mut().filter(|cube| cube.alive()) {
+            // the only unit on a rotator is the pivot.
+            let arrived = std::mem::take(&mut cube.arrived);
+            let mut pivots = cube.units.iter().filter_map(|unit| {
+                let clockwise = tiles.get(&unit.position)?.rotate()?;
+                Some((unit.position, clockwise))
+            });
+            let (Some((pivot, clockwise)), None) = (pivots.next(), pivots.next()) else {
+                continue;
+            };
+
+            // a cube turns once it comes to rest after moving onto it.
+            if cube.moved() {
+                cube.arrived = true;
+                continue;
+            }
+            if !arrived {
+                continue;
+            }
+
+            // the box between each unit and its next position is swept.
+            let body = cube.units.iter().map(|unit| unit.position);
+            let body = body.collect::<HashSet<_>>();
+            let blocked = cube.units.iter().any(|unit| {
+                let (from, to) = (unit.position, unit.position.rotate(pivot, clockwise));
+                let xs = from.x.min(to.x)..=from.x.max(to.x);
+                let ys = from.y.min(to.y)..=from.y.max(to.y);
+                ys.flat_map(|y| xs.clone().map(move |x| Point::new(x, y)))
+                    .any(|o| {
+                        area.blocked(o)
+                            || (!body.contains(&o) && (occupied.contains(&o) || raised(o)))
+                    })
+            });
+            if blocked {
+                continue;
+            }
+
+            for unit in cube.units.iter_mut() {
+                occupied.remove(&unit.position);
+                unit.position = unit.position.rotate(pivot, clockwise);
+            }
+            let collision = HashSetCollision::new(cube.units.iter().map(|unit| unit.position));
+            for unit in cube.units.iter_mut() {
+                occupied.insert(unit.position);
+                unit.neighborhood = collision.neighborhood(unit.position);
+            }
+            cube.contours = Contours::new(&cube.units).into();
+            cube.turned = Some((pivot, clockwise));
+        }
+    }
+
+    fn process_fallen_cubes(&mut self) {
+        let tiles = &self.tiles;
+        let falling = self.falling;
+        let mut pieces = Vec::new();
+        for cube in self.cube.iter_mut().filter(|cube| cube.moved()) {
+            let over = |unit: &Unit| tiles.get(&unit.position).is_some_and(|o| o.pit());
+            if !cube.units.iter().any(over) {
+                continue;
+            }
+
+            let units = std::mem::take(&mut cube.units);
+            let (fallen, rest) = match falling {
+                Falling::Whole => (units, Vec::new()),
+                Falling::Apart => units.into_iter().partition(over),
+            };
+            self.fallen
+                .extend(fallen.into_iter().map(|unit| output::Unit {
+                    id: unit.index,
+                    kind: cube.kind,
+                    position: unit.position,
+                    movement: cube.movement,
+                    constraint: cube.constraint,
+                    neighborhood: unit.neighborhood,
+                    hidden: false,
+                }));
+            cube.units = rest;
+            pieces.extend(cube.split(|_, _| true));
+        }
+
+        for mut piece in pieces {
+            piece.index = self.cube.len();
+            self.cube.push(piece);
+        }
+    }
+
+    fn process_exploded_cubes(&mut self) {
+        const EXPLOSIVE: Kind = Kind::Bomb;
+        const SPENT: Kind = Kind::Crate;
+        let area = &mut self.area;
+        for cube in self.cube.iter_mut().filter(|cube| cube.kind == EXPLOSIVE) {
+            // a bomb goes off only if it knocks on a cube while moving.
+            if cube.movement.is_none() || cube.constraint != Constraint::Slap {
+                continue;
+            }
+
+            let bricks = cube
+                .units
+                .iter()
+                .flat_map(|unit| Movement::ALL.map(|m| unit.position + m.into()))
+                .filter(|&o| area.breakable(o))
+                .collect::<Vec<_>>();
+            if bricks.is_empty() {
+                continue;
+            }
+
+            let area = Arc::make_mut(area);
+            bricks.into_iter().for_each(|o| area.remove(o));
+            cube.kind = SPENT;
+        }
+    }
+
+    fn process_unlocked_cubes(&mut self) {
+        let tiles = &self.tiles;
+        let spent = &mut self.spent;
+        let mut opened = Vec::new();
+        for cube in self.cube.iter() {
+            for unit in cube.units.iter() {
+                let o = unit.position;
+                let key = tiles.get(&o).and_then(|tile| tile.key());
+                if key == Some(cube.kind) && !spent.contains(&o) {
+                    spent.push(o);
+                    opened.push(cube.kind);
+                }
+            }
+        }
+        if opened.is_empty() {
+            return;
+        }
+
+        // a key opens all locks of its kind.
+        let area = Arc::make_mut(&mut self.area);
+        for (&o, tile) in tiles.iter() {
+            if tile.lock().is_some_and(|kind| opened.contains(&kind)) && !spent.contains(&o) {
+                spent.push(o);
+                area.remove(o);
+            }
+        }
+    }
+
     fn process_imbalanced_cubes(&mut self) {
         // prepare to connect
         let number_of_cubes = self.cube.len();
@@ -198,31 +629,60 @@ impl Collection {
         }
     }
 
-    fn process_blocked_cubes(&mut self) -> Digraph {
+    fn process_blocked_cubes(
+        &mut self,
+        pushes: &[(usize, usize)],
+        mut trace: Option<&mut Trace>,
+    ) -> Digraph {
         // prepare
         let number_of_cubes = self.cube.len();
         let mut connection = DisjointSet::new(number_of_cubes);
         let mut successors = Digraph::with_capacity(number_of_cubes);
 
+        // pushed crates follow their pushers.
+        for &(pusher, pushed) in pushes {
+            successors.add(pusher, pushed);
+        }
+
         // find blocked and marks them with Constraint::Stop.
         let territory = Territory::new(self.cube.iter());
         let mut stopped = Vec::new();
+        let walled = |o: &Point| self.area.blocked(*o) || self.blinking.raised(*o, self.round);
         for cube in self.cube.iter().filter_map(Moving::new) {
-            let mut blocked = cube.frontlines().any(|o| self.area.blocked(o));
+            let mut blocked = cube.frontlines().any(|o| walled(&o));
+            if blocked {
+                Self::explain(&mut trace, &cube, Constraint::Stop, || {
+                    let wall = cube.frontlines().find(walled);
+                    Cause::Wall(wall.unwrap_or_default())
+                });
+            }
 
             if !blocked {
                 let neighbors = territory.neighbors_in_front(&cube).collect::<HashSet<_>>();
-                blocked = neighbors
-                    .iter()
-                    .any(|&other| !cube.same_movement(other) && !cube.linkable(other));
+                let opposed = |other: &Cube| !cube.same_movement(other) && !cube.linkable(other);
+                blocked = neighbors.iter().any(|&other| opposed(other));
+                if blocked {
+                    Self::explain(&mut trace, &cube, Constraint::Stop, || {
+                        let other = neighbors.iter().filter(|&&o| opposed(o)).map(|o| o.id());
+                        Cause::Block(other.min().unwrap_or_default())
+                    });
+                }
 
                 if !blocked {
-                    for &other in neighbors.iter() {
-                        if !cube.same_movement(other) && cube.linkable(other) {
-                            blocked = true;
-                            stopped.push(other.index);
-                            connection.join(&cube, other);
-                        }
+                    let linked = |other: &Cube| !cube.same_movement(other) && cube.linkable(other);
+                    for &other in neighbors.iter().filter(|&&o| linked(o)) {
+                        blocked = true;
+                        stopped.push(other.index);
+                        connection.join(&cube, other);
+                        Self::explain(&mut trace, other, Constraint::Stop, || {
+                            Cause::Link(cube.id())
+                        });
+                    }
+                    if blocked {
+                        Self::explain(&mut trace, &cube, Constraint::Stop, || {
+                            let other = neighbors.iter().filter(|&&o| linked(o)).map(|o| o.id());
+                            Cause::Link(other.min().unwrap_or_default())
+                        });
                     }
                 }
 
@@ -245,6 +705,7 @@ impl Collection {
             &successors,
             Constraint::Stop,
             Some(&mut connection),
+            trace,
         )
         .into_iter()
         .for_each(|index| self.cube[index].constraint = Constraint::Stop);
@@ -253,7 +714,11 @@ impl Collection {
         successors
     }
 
-    fn process_conflicted_cubes(&mut self, successors: &Digraph) -> HashSet<(usize, usize)> {
+    fn process_conflicted_cubes(
+        &mut self,
+        successors: &Digraph,
+        mut trace: Option<&mut Trace>,
+    ) -> HashSet<(usize, usize)> {
         let number_of_cubes = self.cube.len();
         let mut connection = DisjointSet::new(number_of_cubes);
         let mut conflict = Conflict::with_capacity(number_of_cubes);
@@ -279,6 +744,9 @@ impl Collection {
                 let next = race[(i + /* **/ 1) % size];
                 if Conflict::locked(cube, it, prev) || Conflict::locked(cube, it, next) {
                     locked.insert(it);
+                    Self::explain(&mut trace, &cube[it], Constraint::Lock, || {
+                        Cause::Race(race.map(|o| o.map(|i| cube[i].id())))
+                    });
                     continue;
                 }
 
@@ -295,15 +763,26 @@ impl Collection {
             }
         }
 
-        self.conduct(locked, &successors, Constraint::Lock, Some(&mut connection))
-            .into_iter()
-            .for_each(|index| self.cube[index].constraint = Constraint::Lock);
+        self.conduct(
+            locked,
+            &successors,
+            Constraint::Lock,
+            Some(&mut connection),
+            trace,
+        )
+        .into_iter()
+        .for_each(|index| self.cube[index].constraint = Constraint::Lock);
         self.link(&mut connection);
 
         competed
     }
 
-    fn process_competed_cubes(&mut self, successors: &Digraph, competed: HashSet<(usize, usize)>) {
+    fn process_competed_cubes(
+        &mut self,
+        successors: &Digraph,
+        competed: HashSet<(usize, usize)>,
+        mut trace: Option<&mut Trace>,
+    ) {
         // clean balanced status
         self.cube.iter_mut().for_each(|cube| cube.balanced = false);
 
@@ -351,13 +830,22 @@ impl Collection {
                         .map(|c| c.movement);
 
                     if let Some(movement) = Agreement::vote(iter).unwrap_or_default() {
-                        for index in group {
-                            let cube = &mut self.cube[index];
+                        for &index in group.iter() {
+                            let cube = &self.cube[index];
                             if cube.kind != kind
                                 && cube.constraint < Constraint::Slap
                                 && cube.movement != Some(movement)
                             {
                                 loser.insert(index);
+                                Self::explain(&mut trace, cube, Constraint::Slap, || {
+                                    let winner = group
+                                        .iter()
+                                        .map(|&i| &self.cube[i])
+                                        .filter(|c| c.kind == kind && c.movement == Some(movement))
+                                        .map(Cube::id)
+                                        .min();
+                                    Cause::Absorb(winner.unwrap_or_default())
+                                });
                             }
                         }
                     }
@@ -368,16 +856,22 @@ impl Collection {
         }
 
         for (l, r) in competed {
-            let c = &mut self.cube;
+            let c = &self.cube;
             if c[l].constraint < Constraint::Slap && c[r].constraint < Constraint::Slap {
                 loser.insert(r);
+                Self::explain(&mut trace, &c[r], Constraint::Slap, || {
+                    Cause::Compete(c[l].id())
+                });
                 if !c[l].absorbable(&c[r]) {
                     loser.insert(l);
+                    Self::explain(&mut trace, &c[l], Constraint::Slap, || {
+                        Cause::Compete(c[r].id())
+                    });
                 }
             }
         }
 
-        self.conduct(loser, &successors, Constraint::Lock, None)
+        self.conduct(loser, &successors, Constraint::Lock, None, trace)
             .into_iter()
             .for_each(|index| self.cube[index].constraint = Constraint::Slap);
     }
@@ -401,6 +895,7 @@ impl Collection {
         successors: &Digraph,
         constraint: Constraint,
         connection: Option<&mut DisjointSet>,
+        trace: Option<&mut Trace>,
     ) -> HashSet<usize> {
         let number_of_cubes = self.cube.len();
         let mut queue = VecDeque::with_capacity(number_of_cubes);
@@ -420,6 +915,9 @@ impl Collection {
                 {
                     if visit.insert(successor.index) {
                         queue.push_back(successor);
+                        if let Some(&mut ref mut trace) = trace {
+                            trace.follow(precursor.id(), successor.ids());
+                        }
                     }
 
                     if let Some(&mut ref mut connection) = connection {
@@ -434,6 +932,15 @@ impl Collection {
         visit
     }
 
+    fn explain<F>(trace: &mut Option<&mut Trace>, cube: &Cube, constraint: Constraint, cause: F)
+    where
+        F: FnOnce() -> Cause,
+    {
+        if let Some(trace) = trace {
+            trace.record(cube.ids(), constraint, cause());
+        }
+    }
+
     fn link(&mut self, connection: &mut DisjointSet) {
         for group in connection.groups() {
             let mut arena = Arena::new();
@@ -449,6 +956,17 @@ impl Collection {
     fn merge(&mut self, from: Vec<usize>, kind: Kind) {
         let cube = &mut self.cube;
 
+        // motions drive units of their own cubes, even after merging.
+        let motion = {
+            let mut others = Vec::with_capacity(from.len());
+            for &i in from.iter() {
+                if cube[i].kind == kind {
+                    let units = cube[i].units.iter().map(|unit| unit.index).collect();
+                    others.push((Motion::take(&mut cube[i].motion), units));
+                }
+            }
+            Motion::from_iter(others.into_iter())
+        };
         let units = {
             let capacity = from.iter().map(|&i| cube[i].units.len()).sum::<usize>();
             let mut units = Vec::with_capacity(capacity);
@@ -461,15 +979,6 @@ impl Collection {
             }
             units
         };
-        let motion = {
-            let mut others = Vec::with_capacity(from.len());
-            for &i in from.iter() {
-                if cube[i].kind == kind {
-                    others.push(Motion::take(&mut cube[i].motion));
-                }
-            }
-            Motion::from_iter(others.into_iter())
-        };
         let contours = Contours::new(&units).into();
         let movement = Agreement::vote(
             from.iter()
@@ -487,6 +996,10 @@ impl Collection {
             constraint
         };
 
+        let arrived = from
+            .iter()
+            .any(|&i| cube[i].kind == kind && cube[i].arrived);
+
         if let Some(&index) = from.first() {
             cube[index] = Cube {
                 index,
@@ -497,11 +1010,44 @@ impl Collection {
                 balanced: false,
                 movement,
                 constraint,
+                arrived,
+                turned: None,
             };
         }
     }
 }
 
+/// StateKey contains everything that affects the future of a collection, so
+/// that equal keys always lead to the same rounds. Besides cubes, it keeps
+/// indexes of removed frozen cells, picked keys with opened locks, and the
+/// round in the cycle of blinkers.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StateKey(Vec<CubeKey>, Vec<usize>, Vec<Point>, usize);
+
+type CubeKey = (Kind, Vec<(usize, Point)>, Motion, bool);
+
+impl StateKey {
+    /// Alive cubes with their units of (index, position).
+    pub(crate) fn cubes(&self) -> impl Iterator<Item = (Kind, &[(usize, Point)])> {
+        self.0.iter().map(|cube| (cube.0, cube.1.as_slice()))
+    }
+
+    /// Indexes of removed frozen cells, in order.
+    pub(crate) fn broken(&self) -> &[usize] {
+        &self.1
+    }
+
+    /// Points of picked keys and opened locks.
+    pub(crate) fn spent(&self) -> &[Point] {
+        &self.2
+    }
+
+    /// The round in the cycle of blinkers.
+    pub(crate) fn round(&self) -> usize {
+        self.3
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // internal
 
@@ -511,10 +1057,12 @@ struct Cube {
     kind: Kind,
     units: Vec<Unit>,
     motion: Motion,
-    contours: Arc<Contours>,    // calculated boundary points
-    balanced: bool,             // state of being unabsorbable
-    movement: Option<Movement>, // original movement direction
-    constraint: Constraint,     // state of movement
+    contours: Arc<Contours>,       // calculated boundary points
+    balanced: bool,                // state of being unabsorbable
+    movement: Option<Movement>,    // original movement direction
+    constraint: Constraint,        // state of movement
+    arrived: bool,                 // moved onto a rotator in the last round
+    turned: Option<(Point, bool)>, // the rotator which turned it in this round
 }
 
 impl Cube {
@@ -522,8 +1070,25 @@ impl Cube {
         !self.units.is_empty()
     }
 
+    fn id(&self) -> usize {
+        self.ids().min().unwrap_or_default()
+    }
+
+    fn ids(&self) -> impl Iterator<Item = usize> + Clone + '_ {
+        self.units.iter().map(|unit| unit.index)
+    }
+
+    fn moved(&self) -> bool {
+        self.constraint == Constraint::Free && self.movement.is_some()
+    }
+
     fn unstable(&self) -> bool {
-        !self.balanced && !matches!(self.kind, Kind::White) && self.alive()
+        !self.balanced
+            && !matches!(
+                self.kind,
+                Kind::White | Kind::Crate | Kind::Brick | Kind::Bomb
+            )
+            && self.alive()
     }
 
     const fn linkable(&self, other: &Self) -> bool {
@@ -537,6 +1102,79 @@ impl Cube {
     fn same_movement(&self, other: &Self) -> bool {
         self.movement == other.movement
     }
+
+    /// Split units into pieces through links for which `linked` is true.
+    /// The first piece stays, and the others are returned. Each piece gets
+    /// the part of the motion driving its units, see `Motion::split`.
+    /// Neighborhoods are always calculated again.
+    fn split<F>(&mut self, linked: F) -> Vec<Cube>
+    where
+        F: Fn(Point, Point) -> bool,
+    {
+        let index = self
+            .units
+            .iter()
+            .enumerate()
+            .map(|(i, unit)| (unit.position, i))
+            .collect::<HashMap<_, _>>();
+        let mut group = vec![usize::MAX; self.units.len()];
+        let mut count = 0;
+        for i in 0..self.units.len() {
+            if group[i] != usize::MAX {
+                continue;
+            }
+            group[i] = count;
+            let mut stack = vec![i];
+            while let Some(j) = stack.pop() {
+                let a = self.units[j].position;
+                for movement in Movement::ALL {
+                    let b = a + movement.into();
+                    match index.get(&b) {
+                        Some(&k) if group[k] == usize::MAX && linked(a, b) => {
+                            group[k] = count;
+                            stack.push(k);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        let mut split = vec![Vec::new(); count];
+        for (unit, g) in std::mem::take(&mut self.units).into_iter().zip(group) {
+            split[g].push(unit);
+        }
+        let mut pieces = Vec::with_capacity(count.saturating_sub(1));
+        let whole = self.motion.take();
+        for (g, mut units) in split.into_iter().enumerate() {
+            let collision = HashSetCollision::new(units.iter().map(|unit| unit.position));
+            for unit in units.iter_mut() {
+                unit.neighborhood = collision.neighborhood(unit.position);
+            }
+            let contours = Contours::new(&units).into();
+            let motion = whole.split(&units.iter().map(|unit| unit.index).collect::<Vec<_>>());
+            if g == 0 {
+                self.units = units;
+                self.contours = contours;
+                self.motion = motion;
+            } else {
+                pieces.push(Cube {
+                    index: self.index,
+                    kind: self.kind,
+                    units,
+                    motion,
+                    contours,
+                    balanced: false,
+                    movement: self.movement,
+                    constraint: self.constraint,
+                    arrived: self.arrived,
+                    turned: self.turned,
+                });
+            }
+        }
+        pieces
+    }
 }
 
 impl std::hash::Hash for Cube {
@@ -837,7 +1475,7 @@ impl Arena {
     const fn kind_to_index(kind: Kind) -> Option<usize> {
         use Kind::*;
         match kind {
-            White => None,
+            White | Crate | Brick | Bomb => None,
             Red => Some(0),
             Blue => Some(1),
             Green => Some(2),
@@ -0,0 +1,530 @@
+//! Conformance cases of collision rules. Each case is a board, an input and
+//! the board after one round. Cubes with a movement in `before` move that
+//! way by a command of one step, and green cubes also follow the input.
+
+use crate::{board::Board, cube::Movement, seed::Seed, state::CubeCore};
+
+struct Case {
+    name: &'static str,
+    input: Option<Movement>,
+    before: &'static str,
+    after: &'static str,
+}
+
+const R: Option<Movement> = Some(Movement::Right);
+
+const CASES: &[Case] = &[
+    // `process_imbalanced_cubes`: cubes nearby absorb each other
+    Case {
+        name: "green absorbs blue nearby",
+        input: None,
+        before: "
+            **********
+            *GB*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *G-*..*..*
+            **********
+        ",
+    },
+    Case {
+        name: "red absorbs green nearby",
+        input: None,
+        before: "
+            **********
+            *RG*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *R-*..*..*
+            **********
+        ",
+    },
+    Case {
+        name: "blue absorbs red nearby",
+        input: None,
+        before: "
+            **********
+            *RB*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *B-*..*..*
+            **********
+        ",
+    },
+    Case {
+        name: "absorption spreads through cubes of the same kind",
+        input: None,
+        before: "
+            *************
+            *GBB*...*...*
+            *************
+        ",
+        after: "
+            *************
+            *G--*...*...*
+            *************
+        ",
+    },
+    Case {
+        name: "three kinds balance each other",
+        input: None,
+        before: "
+            *************
+            *RGB*...*...*
+            *************
+        ",
+        after: "
+            *************
+            *RGB*...*...*
+            *************
+        ",
+    },
+    Case {
+        name: "same kinds do not merge at rest",
+        input: None,
+        before: "
+            **********
+            *GG*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *GG*..*..*
+            **********
+        ",
+    },
+    Case {
+        name: "white is never absorbed",
+        input: None,
+        before: "
+            **********
+            *GW*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *GW*..*..*
+            **********
+        ",
+    },
+    // `process_pushed_cubes`: moving cubes push crates
+    Case {
+        name: "crates are pushed",
+        input: R,
+        before: "
+            *************
+            *GC *.. *.. *
+            *************
+        ",
+        after: "
+            *************
+            * GC* >>* ..*
+            *************
+        ",
+    },
+    Case {
+        name: "rows of crates are pushed through",
+        input: R,
+        before: "
+            ****************
+            *GCC *... *... *
+            ****************
+        ",
+        after: "
+            ****************
+            * GCC* >>>* ...*
+            ****************
+        ",
+    },
+    Case {
+        name: "blocked crates stop the pushers",
+        input: R,
+        before: "
+            *************
+            *GCC*...*...*
+            *************
+        ",
+        after: "
+            *************
+            *GCC*>>>*SSS*
+            *************
+        ",
+    },
+    Case {
+        name: "stopped pushers hold the crates",
+        input: R,
+        before: "
+            *************
+            *GC *.. *.. *
+            *|W *.. *.. *
+            *************
+        ",
+        after: "
+            *************
+            *GC *>> *SS *
+            *|W *>. *S. *
+            *************
+        ",
+    },
+    Case {
+        name: "crates pushed in different directions stay",
+        input: R,
+        before: "
+            *************
+            *GCW*..<*...*
+            *************
+        ",
+        after: "
+            *************
+            *GCW*>.<*S.S*
+            *************
+        ",
+    },
+    Case {
+        name: "crates neither merge nor absorb",
+        input: None,
+        before: "
+            *******************
+            *CCGCB*.....*.....*
+            *******************
+        ",
+        after: "
+            *******************
+            *CCGCB*.....*.....*
+            *******************
+        ",
+    },
+    Case {
+        name: "pushed crates race like other cubes",
+        input: R,
+        before: "
+            ****************
+            *GC W*.. <*.. .*
+            ****************
+        ",
+        after: "
+            ****************
+            *GC W*>> <*KK K*
+            ****************
+        ",
+    },
+    // `process_blocked_cubes`: blocked cubes and their followers get Stop
+    Case {
+        name: "stopped by borders",
+        input: R,
+        before: "
+            *******
+            *G*.*.*
+            *******
+        ",
+        after: "
+            *******
+            *G*>*S*
+            *******
+        ",
+    },
+    Case {
+        name: "stopped by walls",
+        input: R,
+        before: "
+            **********
+            *GW*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *GW*>.*S.*
+            **********
+        ",
+    },
+    Case {
+        name: "stopped by a cube moving elsewhere",
+        input: R,
+        before: "
+            **********
+            *GW*.v*..*
+            *  *  *  *
+            **********
+        ",
+        after: "
+            **********
+            *G *> *S *
+            * W* v* .*
+            **********
+        ",
+    },
+    Case {
+        name: "stopped by balanced cubes",
+        input: R,
+        before: "
+            *************
+            *RGB*...*...*
+            *************
+        ",
+        after: "
+            *************
+            *RGB*.>.*.S.*
+            *************
+        ",
+    },
+    Case {
+        name: "linked to a resting cube of the same kind",
+        input: None,
+        before: "
+            **********
+            *GG*>.*..*
+            **********
+        ",
+        after: "
+            **********
+            *G-*..*SS*
+            **********
+        ",
+    },
+    Case {
+        name: "linked to a cube of the same kind moving the other way",
+        input: None,
+        before: "
+            **********
+            *GG*><*..*
+            **********
+        ",
+        after: "
+            **********
+            *G-*..*SS*
+            **********
+        ",
+    },
+    Case {
+        name: "followers stop and link with the leader",
+        input: R,
+        before: "
+            **********
+            *GG*..*..*
+            **********
+        ",
+        after: "
+            **********
+            *G-*>>*SS*
+            **********
+        ",
+    },
+    Case {
+        name: "followers of another kind stop without linking",
+        input: R,
+        before: "
+            **********
+            *GW*.>*..*
+            **********
+        ",
+        after: "
+            **********
+            *GW*>>*SS*
+            **********
+        ",
+    },
+    Case {
+        name: "followers move with the leader",
+        input: R,
+        before: "
+            *************
+            *GG *.. *.. *
+            *************
+        ",
+        after: "
+            *************
+            * GG* >>* ..*
+            *************
+        ",
+    },
+    // `process_conflicted_cubes`: perpendicular racers get Lock
+    Case {
+        name: "perpendicular racers lock each other",
+        input: R,
+        before: "
+            **********
+            * W* v* .*
+            *G *. *. *
+            **********
+        ",
+        after: "
+            **********
+            * W* v* L*
+            *G *> *L *
+            **********
+        ",
+    },
+    Case {
+        name: "followers lock and link with the leader",
+        input: R,
+        before: "
+            *************
+            *  W*  v*  .*
+            *GG *.. *.. *
+            *************
+        ",
+        after: "
+            *************
+            *  W*  v*  L*
+            *G- *>> *LL *
+            *************
+        ",
+    },
+    Case {
+        name: "the absorber wins a perpendicular race",
+        input: R,
+        before: "
+            **********
+            * B* v* .*
+            *G *. *. *
+            **********
+        ",
+        after: "
+            **********
+            * B* v* L*
+            * G* >* .*
+            **********
+        ",
+    },
+    // `process_competed_cubes`: losers of head-on races get Slap
+    Case {
+        name: "head-on racers knock each other",
+        input: R,
+        before: "
+            *************
+            *G W*. <*. .*
+            *************
+        ",
+        after: "
+            *************
+            *G W*> <*K K*
+            *************
+        ",
+    },
+    Case {
+        name: "followers knock with the leader",
+        input: R,
+        before: "
+            ****************
+            *GG W*.. <*.. .*
+            ****************
+        ",
+        after: "
+            ****************
+            *GG W*>> <*KK K*
+            ****************
+        ",
+    },
+    Case {
+        name: "the absorbed cannot flee",
+        input: R,
+        before: "
+            *************
+            *G B*. <*. .*
+            *************
+        ",
+        after: "
+            *************
+            * GB* ><* .K*
+            *************
+        ",
+    },
+    Case {
+        name: "balanced racers knock each other",
+        input: R,
+        before: "
+            *************
+            *G B*. <*. .*
+            * R * . * . *
+            *************
+        ",
+        after: "
+            *************
+            *G B*> <*K K*
+            * R * . * . *
+            *************
+        ",
+    },
+    Case {
+        name: "no one loses in a disagreement",
+        input: None,
+        before: "
+            *************
+            *G  *>  *.  *
+            * B * . * . *
+            *  G*  <*  .*
+            *************
+        ",
+        after: "
+            *************
+            * G * > * . *
+            * B * . * . *
+            * G * < * . *
+            *************
+        ",
+    },
+    // `process_exploded_cubes`: bombs knocking on cubes break bricks
+    Case {
+        name: "stopped bombs stay",
+        input: R,
+        before: "
+            *************
+            *GOK*...*...*
+            *************
+        ",
+        after: "
+            *************
+            *GOK*>>.*SS.*
+            *************
+        ",
+    },
+    Case {
+        name: "knocking bombs break bricks",
+        input: R,
+        before: "
+            ****************
+            *GO W*.. <*.. .*
+            * K  * .  * .  *
+            ****************
+        ",
+        after: "
+            ****************
+            *GC W*>> <*KK K*
+            *    *    *    *
+            ****************
+        ",
+    },
+];
+
+#[test]
+fn conformance() {
+    let mut failures = Vec::new();
+    for case in CASES {
+        let before = case.before.parse::<Board>();
+        let after = case.after.parse::<Board>();
+        let (before, after) = match (before, after) {
+            (Ok(before), Ok(after)) => (before, after),
+            (Err(e), _) | (_, Err(e)) => {
+                failures.push(format!("{}: {}", case.name, e));
+                continue;
+            }
+        };
+
+        let mut game = CubeCore::new(&Seed::from(&before));
+        game.commit(case.input).for_each(drop);
+        let output = Board::from(&game);
+        if output != after {
+            failures.push(format!(
+                "{}:\nbefore\n{}input {:?}\nexpected\n{}but got\n{}",
+                case.name, before, case.input, after, output
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
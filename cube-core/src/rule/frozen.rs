@@ -1,33 +1,55 @@
+use std::sync::Arc;
+
 use super::{
     extension::CollisionExtension,
     lookup::{BitmapCollision, Collision},
 };
-use crate::cube::{Neighborhood, Point};
+use crate::cube::{Kind, Neighborhood, Point};
+
+/// A frozen unit of (kind, position, neighborhood).
+pub type Cell = (Kind, Point, Neighborhood);
 
-#[derive(Debug)]
+/// Frozen keeps units which never move. Cells are shared by clones, and
+/// only bricks and locks can be removed from them, see `Frozen::remove`.
+/// Locks are white cells after all others.
+#[derive(Clone, Debug)]
 pub struct Frozen {
-    unchanged: Box<[(Point, Neighborhood)]>,
+    unchanged: Arc<[Cell]>,
+    locked: usize,      // index of the first lock
+    broken: Vec<usize>, // indexes of removed cells, in order
     collision: BitmapCollision,
 }
 
 impl Frozen {
-    pub fn new<'a, I>(width: usize, height: usize, it: I) -> Self
+    pub fn new<'a, I>(width: usize, height: usize, it: I, locks: &'a [Point]) -> Self
     where
-        I: Iterator<Item = &'a [Point]>,
+        I: Iterator<Item = (Kind, &'a [Point])>,
     {
+        // bricks and locks are removed one by one, so that each of them is
+        // a cube.
+        let pieces = it.flat_map(|(kind, os)| match kind {
+            Kind::Brick => os.chunks(1).map(|o| (kind, o)).collect::<Vec<_>>(),
+            _ => vec![(kind, os)],
+        });
+        let locks = locks.chunks(1).map(|o| (Kind::White, o));
+
         let mut collision = BitmapCollision::new(width, height);
-        let cubes = {
-            let build = |os: &'a [Point]| {
-                let mut c = BitmapCollision::new(width, height);
-                os.iter().for_each(|&o| c.put(o));
-                collision.or(&c);
-                os.iter().map(move |&o| (o, c.neighborhood_or_border(o)))
-            };
-            it.flat_map(build).collect::<Box<_>>()
+        let mut build = |(kind, os): (Kind, &'a [Point])| {
+            let mut c = BitmapCollision::new(width, height);
+            os.iter().for_each(|&o| c.put(o));
+            collision.or(&c);
+            os.iter()
+                .map(move |&o| (kind, o, c.neighborhood_or_border(o)))
+                .collect::<Vec<_>>()
         };
+        let mut cubes = pieces.flat_map(&mut build).collect::<Vec<_>>();
+        let locked = cubes.len();
+        cubes.extend(locks.flat_map(&mut build));
 
         Self {
-            unchanged: cubes,
+            unchanged: cubes.into(),
+            locked,
+            broken: Vec::new(),
             collision,
         }
     }
@@ -36,11 +58,55 @@ impl Frozen {
         !self.collision.available(point)
     }
 
-    pub fn iter<'a>(&'a self) -> std::slice::Iter<'a, (Point, Neighborhood)> {
-        self.unchanged.iter()
+    /// Whether there is a brick at the point.
+    pub fn breakable(&self, point: Point) -> bool {
+        self.find(point, Kind::Brick).is_some()
+    }
+
+    /// Remove the brick or the lock at the point, and nothing happens to
+    /// other cells.
+    pub fn remove(&mut self, point: Point) {
+        let lock = self
+            .iter()
+            .find(|(index, cell)| *index >= self.locked && cell.1 == point);
+        let index = self.find(point, Kind::Brick);
+        if let Some(index) = index.or(lock.map(|(index, _)| index)) {
+            let at = self.broken.binary_search(&index).unwrap_or_else(|at| at);
+            self.broken.insert(at, index);
+            self.collision.remove(point);
+        }
+    }
+
+    /// Indexes of removed cells, in order.
+    pub fn broken(&self) -> &[usize] {
+        &self.broken
+    }
+
+    /// Whether both are made from the same cells, whatever has been removed.
+    pub fn same_source(&self, that: &Self) -> bool {
+        Arc::ptr_eq(&self.unchanged, &that.unchanged)
+    }
+
+    /// The cell of the index, even if it has been removed.
+    pub fn get(&self, index: usize) -> &Cell {
+        &self.unchanged[index]
+    }
+
+    /// Remaining cells with their indexes.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Cell)> + '_ {
+        let broken = &self.broken;
+        self.unchanged
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| broken.binary_search(index).is_err())
     }
 
     pub fn len(&self) -> usize {
+        self.unchanged.len() - self.broken.len()
+    }
+
+    /// Number of cells, including removed ones.
+    pub fn capacity(&self) -> usize {
         self.unchanged.len()
     }
 
@@ -51,4 +117,10 @@ impl Frozen {
     pub fn height(&self) -> usize {
         self.collision.height()
     }
+
+    fn find(&self, point: Point, kind: Kind) -> Option<usize> {
+        self.iter()
+            .find(|(_, cell)| cell.0 == kind && cell.1 == point)
+            .map(|(index, _)| index)
+    }
 }
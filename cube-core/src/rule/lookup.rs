@@ -12,6 +12,7 @@ pub trait Collision {
     fn existed(&self, point: Point) -> bool;
     fn available(&self, point: Point) -> bool;
     fn put(&mut self, point: Point);
+    fn remove(&mut self, point: Point);
 }
 
 pub struct HashSetCollision(HashSet<Point>);
@@ -34,6 +35,10 @@ impl Collision for HashSetCollision {
     fn put(&mut self, point: Point) {
         self.0.insert(point);
     }
+
+    fn remove(&mut self, point: Point) {
+        self.0.remove(&point);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -101,6 +106,12 @@ impl Collision for BitmapCollision {
             self.bits[index] |= 1 << delta;
         }
     }
+
+    fn remove(&mut self, point: Point) {
+        if let Some((index, delta)) = self.collapse(point) {
+            self.bits[index] &= !(1 << delta);
+        }
+    }
 }
 
 /////////////////////////////////////////////////////////////////////////////
@@ -133,6 +144,11 @@ impl DisjointSet {
         }
     }
 
+    /// The representative of the group which the index belongs to.
+    pub fn find(&mut self, index: impl Into<usize>) -> usize {
+        *self.root_mut(index.into())
+    }
+
     pub fn groups(&mut self) -> DisjointSetGroups {
         let hint = self.existed.len();
         let mut pair = HashMap::with_capacity(hint);
@@ -291,5 +307,11 @@ mod tests {
 
             assert_eq!(case.2, out, "case {}", i);
         }
+
+        let mut lookup = DisjointSet::new(4);
+        lookup.join(0usize, 2usize);
+        assert_eq!(lookup.find(0usize), lookup.find(2usize));
+        assert_ne!(lookup.find(0usize), lookup.find(1usize));
+        assert_eq!(lookup.find(3usize), 3);
     }
 }
@@ -8,6 +8,7 @@ pub struct Unit {
     pub movement: Option<Movement>,
     pub constraint: Constraint,
     pub neighborhood: Neighborhood,
+    pub hidden: bool, // out of the game for now, e.g. a lowered blinker
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -18,4 +19,33 @@ pub struct Diff {
     pub movement: Option<Option<Movement>>,
     pub constraint: Option<Constraint>,
     pub neighborhood: Option<Neighborhood>,
+    pub hidden: Option<bool>,
+    pub pivot: Option<(Point, bool)>, // the rotator which turned it, and whether clockwise
+    pub removed: bool,                // the unit is gone, and no more diffs follow unless remade
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cause {
+    /// a wall or the border at the point is on the path
+    Wall(Point),
+    /// a cube moving in another direction is on the path
+    Block(usize),
+    /// linked with a cube moving in another direction
+    Link(usize),
+    /// moving onto the same point with other cubes, which are listed by
+    /// their movements: left, down, right and up
+    Race([Option<usize>; 4]),
+    /// competing with a cube for the same point
+    Compete(usize),
+    /// absorbed by a cube moving in another direction
+    Absorb(usize),
+}
+
+/// Explanation tells why a unit is constrained. Cubes are identified by the
+/// smallest id of their units at the moment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    pub constraint: Constraint,
+    pub cause: Cause,
+    pub chain: Vec<usize>, // cubes followed from the cause to this one
 }
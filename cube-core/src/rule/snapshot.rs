@@ -4,94 +4,159 @@ use super::{
     frozen::Frozen,
     output::{Diff, Unit},
 };
-use crate::cube::{Constraint, Kind, Neighborhood, Point};
+use crate::cube::{Constraint, Point};
 
 #[derive(Clone, Debug)]
 pub struct Snapshot {
     active: Vec<Unit>,
+    removed: Vec<bool>,                 // units in active which have gone
+    pivots: Vec<Option<(Point, bool)>>, // rotators which turned units in active
     forzen: Arc<Frozen>,
+    blinking: Vec<Unit>, // blinkers, which are hidden while lowered
 }
 
 impl Snapshot {
-    pub(crate) fn new(active: Vec<Unit>, forzen: Arc<Frozen>) -> Self {
-        Self { active, forzen }
+    pub(crate) fn new(
+        active: Vec<Unit>,
+        removed: Vec<bool>,
+        pivots: Vec<Option<(Point, bool)>>,
+        forzen: Arc<Frozen>,
+        blinking: Vec<Unit>,
+    ) -> Self {
+        Self {
+            active,
+            removed,
+            pivots,
+            forzen,
+            blinking,
+        }
     }
 
     pub fn contains(&self, position: Point) -> bool {
-        self.active.iter().any(|unit| unit.position == position) || self.forzen.blocked(position)
+        let raised = |unit: &Unit| !unit.hidden && unit.position == position;
+        self.units().any(|unit| unit.position == position)
+            || self.forzen.blocked(position)
+            || self.blinking.iter().any(raised)
     }
 
     pub fn differ<'a>(&'a self, that: &'a Self) -> impl Iterator<Item = Diff> + 'a {
         use std::ptr::eq;
         let same = eq(self, that);
-        let same_source = eq(self.forzen.as_ref(), that.forzen.as_ref());
+        let same_source = self.forzen.same_source(&that.forzen);
         let comparable = same_source && self.active.len() == that.active.len();
         let maximum = (!same && comparable) as usize * self.active.len();
 
-        std::iter::zip(self.active.iter(), that.active.iter())
+        // frozen cells are removed, and they come back only if the game is
+        // made again from an earlier round.
+        let offset = that.active.len();
+        let (l, r) = match !same && comparable {
+            true => (self.forzen.broken(), that.forzen.broken()),
+            false => (&[][..], &[][..]),
+        };
+        let removed = r
+            .iter()
+            .filter(|index| l.binary_search(index).is_err())
+            .map(move |index| Diff {
+                id: index + offset,
+                removed: true,
+                ..Default::default()
+            });
+        let restored = l
+            .iter()
+            .filter(|index| r.binary_search(index).is_err())
+            .map(move |&index| {
+                let cell = that.forzen.get(index);
+                Diff {
+                    id: index + offset,
+                    kind: Some(cell.0),
+                    position: Some(cell.1),
+                    neighborhood: Some(cell.2),
+                    ..Default::default()
+                }
+            });
+
+        // so do units, which are reported as a whole.
+        let moved = std::iter::zip(self.pairs(), that.pairs())
+            .zip(that.pivots.iter().cloned())
+            .map(|((l, r), pivot)| (l, r, pivot))
             .take(maximum)
-            .filter(|(l, r)| {
-                l.kind != r.kind
+            .filter(|((l, gone), (r, removed), _)| {
+                let changed = *removed
+                    || l.kind != r.kind
                     || l.position != r.position
                     || l.movement != r.movement
                     || l.constraint != r.constraint
-                    || l.neighborhood != r.neighborhood
+                    || l.neighborhood != r.neighborhood;
+                (!gone && changed) || (*gone && !removed)
             })
-            .map(|(l, r)| Diff {
+            .map(|((l, gone), (r, removed), pivot)| Diff {
                 id: r.id,
-                kind: (l.kind != r.kind).then(|| r.kind),
-                position: (l.position != r.position).then(|| r.position),
-                movement: (l.movement != r.movement).then(|| r.movement),
-                constraint: (l.constraint != r.constraint).then(|| r.constraint),
-                neighborhood: (l.neighborhood != r.neighborhood).then(|| r.neighborhood),
-            })
+                kind: (gone || l.kind != r.kind).then(|| r.kind),
+                position: (gone || l.position != r.position).then(|| r.position),
+                movement: (gone || l.movement != r.movement).then(|| r.movement),
+                constraint: (gone || l.constraint != r.constraint).then(|| r.constraint),
+                neighborhood: (gone || l.neighborhood != r.neighborhood).then(|| r.neighborhood),
+                hidden: None,
+                pivot,
+                removed,
+            });
+
+        // blinkers are only raised or lowered.
+        let blinked = std::iter::zip(self.blinking.iter(), that.blinking.iter())
+            .take((!same && comparable) as usize * self.blinking.len())
+            .filter(|(l, r)| l.hidden != r.hidden)
+            .map(|(_, r)| Diff {
+                id: r.id,
+                hidden: Some(r.hidden),
+                ..Default::default()
+            });
+        moved.chain(removed).chain(restored).chain(blinked)
     }
 
     pub fn iter(&self) -> SnapshotIter<'_> {
+        let offset = self.active.len();
+        let frozen = self.forzen.iter().map(move |(index, cell)| Unit {
+            id: index + offset,
+            kind: cell.0,
+            position: cell.1,
+            movement: None,
+            constraint: Constraint::Free,
+            neighborhood: cell.2,
+            hidden: false,
+        });
+        let blinking = self.blinking.iter().cloned();
         SnapshotIter {
-            source: self,
-            primary: Some(self.active.iter()),
-            secondary: Some(self.forzen.iter().enumerate()),
+            inner: Box::new(self.units().cloned().chain(frozen).chain(blinking)),
+            size: self.units().count() + self.forzen.len() + self.blinking.len(),
         }
     }
+
+    /// Units in active with whether they have gone.
+    fn pairs(&self) -> impl Iterator<Item = (&Unit, bool)> {
+        std::iter::zip(self.active.iter(), self.removed.iter().cloned())
+    }
+
+    /// Units in active which are still there.
+    fn units(&self) -> impl Iterator<Item = &Unit> {
+        self.pairs().filter(|o| !o.1).map(|o| o.0)
+    }
 }
 
 pub struct SnapshotIter<'a> {
-    source: &'a Snapshot,
-    primary: Option<std::slice::Iter<'a, Unit>>,
-    secondary: Option<std::iter::Enumerate<std::slice::Iter<'a, (Point, Neighborhood)>>>,
+    inner: Box<dyn Iterator<Item = Unit> + 'a>,
+    size: usize,
 }
 
 impl<'a> Iterator for SnapshotIter<'a> {
     type Item = Unit;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let x = self.source.active.len() + self.source.forzen.len();
-        (x, Some(x))
+        (self.size, Some(self.size))
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(iter) = &mut self.primary {
-            if let Some(output) = iter.next() {
-                return Some(output.clone());
-            }
-            self.primary = None
-        }
-
-        if let Some(iter) = &mut self.secondary {
-            if let Some((index, (point, neighborhood))) = iter.next() {
-                return Some(Unit {
-                    id: index + self.source.active.len(),
-                    kind: Kind::White,
-                    position: point.clone(),
-                    movement: None,
-                    constraint: Constraint::Free,
-                    neighborhood: neighborhood.clone(),
-                });
-            }
-            self.secondary = None
-        }
-
-        None
+        let output = self.inner.next();
+        self.size -= output.is_some() as usize;
+        output
     }
 }
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use super::output::{Cause, Explanation};
+use crate::cube::Constraint;
+
+/// Trace collects the reasons why units are constrained in one round. Each
+/// record is keyed by unit id, as cubes may be merged during the round.
+#[derive(Debug, Default)]
+pub struct Trace(HashMap<usize, Explanation>);
+
+impl Trace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Explanation> {
+        self.0.get(&id)
+    }
+
+    /// Record a direct reason. The first reason of the same constraint wins.
+    pub fn record<I>(&mut self, units: I, constraint: Constraint, cause: Cause)
+    where
+        I: Iterator<Item = usize>,
+    {
+        let explanation = Explanation {
+            constraint,
+            cause,
+            chain: Vec::new(),
+        };
+        self.put(units, explanation);
+    }
+
+    /// Record that `successor` is constrained since it follows `precursor`.
+    pub fn follow<I>(&mut self, precursor: usize, successor: I)
+    where
+        I: Iterator<Item = usize>,
+    {
+        if let Some(mut explanation) = self.0.get(&precursor).cloned() {
+            explanation.chain.push(precursor);
+            self.put(successor, explanation);
+        }
+    }
+
+    /// Make records of a cube consistent with its final constraint.
+    pub fn settle<I>(&mut self, units: I, constraint: Constraint)
+    where
+        I: Iterator<Item = usize> + Clone,
+    {
+        if constraint == Constraint::Free {
+            for id in units {
+                self.0.remove(&id);
+            }
+        } else if let Some(explanation) = units
+            .clone()
+            .filter_map(|id| self.0.get(&id).map(|x| (id, x)))
+            .filter(|(_, x)| x.constraint == constraint)
+            .min_by_key(|(id, _)| *id)
+            .map(|(_, x)| x.clone())
+        {
+            for id in units {
+                match self.0.get(&id) {
+                    Some(x) if x.constraint == constraint => {}
+                    _ => {
+                        self.0.insert(id, explanation.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    fn put<I>(&mut self, units: I, explanation: Explanation)
+    where
+        I: Iterator<Item = usize>,
+    {
+        for id in units {
+            match self.0.get(&id) {
+                Some(x) if x.constraint == explanation.constraint => {}
+                _ => {
+                    self.0.insert(id, explanation.clone());
+                }
+            }
+        }
+    }
+}
@@ -1,34 +1,527 @@
-use super::cube::{Kind, Movement, Point};
+use std::collections::HashSet;
 
-#[derive(Clone)]
+use super::{
+    cube::{Blink, Falling, Kind, Mirror, Movement, Point, Pursuit, Tile},
+    glyph,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Seed {
     pub info: Info,
     pub size: Size,
     pub cubes: Vec<Cube>,
     pub destnations: Vec<Point>,
+    pub tiles: Vec<(Point, Tile)>, // floor cells, one at most on a point
+    pub gravity: Option<Movement>, // pulls cubes without other movements
+    pub falling: Falling,          // how cubes fall into pits
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Info {
     pub title: String,
     pub author: String,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Cube {
     pub kind: Kind,
     pub body: Vec<Point>,
     pub command: Option<Command>,
+    pub mirror: Option<Mirror>,
+    pub pursuit: Option<Pursuit>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Size {
     pub width: i32,
     pub height: i32,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Command {
     pub is_loop: bool,
     pub movements: Vec<(Option<Movement>, usize)>,
 }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeedError {
+    /// the width or the height is not positive
+    BadSize(Size),
+    /// the cube of the index has no unit
+    EmptyCube(usize),
+    /// the unit, the destination or the tile is outside of the board
+    Outside(Point),
+    /// more than one unit or tile is placed at the point, or a unit is on
+    /// a lock
+    Overlapped(Point),
+    /// the unit is not connected to the rest of its cube
+    Disconnected(Point),
+    /// no cube is at the point to bind a command
+    Unbound(Point),
+    /// the step of the command of the cube at the point repeats zero times
+    ZeroStep { cube: Point, step: usize },
+}
+
+impl std::fmt::Display for SeedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use SeedError::*;
+        match self {
+            BadSize(o) => write!(f, "size {}x{} is not positive", o.width, o.height),
+            EmptyCube(i) => write!(f, "cube {} has no unit", i),
+            Outside(o) => write!(f, "({}, {}) is outside", o.x, o.y),
+            Overlapped(o) => write!(f, "units overlap at ({}, {})", o.x, o.y),
+            Disconnected(o) => write!(f, "unit ({}, {}) is not connected", o.x, o.y),
+            Unbound(o) => write!(f, "no cube at ({}, {}) to bind", o.x, o.y),
+            ZeroStep { cube, step } => write!(
+                f,
+                "step {} of cube ({}, {}) repeats zero times",
+                step, cube.x, cube.y
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+impl Seed {
+    /// Check whether the seed can be played: the size is positive, bodies
+    /// are connected, units, destinations and tiles are on distinct points
+    /// in the board, no unit is on a lock, and every step of commands moves
+    /// at least once.
+    pub fn check(&self) -> Result<(), SeedError> {
+        use SeedError::*;
+
+        let Size { width, height } = self.size;
+        if width <= 0 || height <= 0 {
+            return Err(BadSize(self.size.clone()));
+        }
+        let inside = |o: &Point| 0 <= o.x && o.x < width && 0 <= o.y && o.y < height;
+
+        let mut visit = HashSet::new();
+        for (i, cube) in self.cubes.iter().enumerate() {
+            if cube.body.is_empty() {
+                return Err(EmptyCube(i));
+            }
+            for &o in cube.body.iter() {
+                if !inside(&o) {
+                    return Err(Outside(o));
+                }
+                if !visit.insert(o) {
+                    return Err(Overlapped(o));
+                }
+            }
+            if let Some(o) = disconnected(&cube.body) {
+                return Err(Disconnected(o));
+            }
+            let mut steps = cube.command.iter().flat_map(|o| o.movements.iter());
+            if let Some(step) = steps.position(|o| o.1 == 0) {
+                let cube = cube.body[0];
+                return Err(ZeroStep { cube, step });
+            }
+        }
+        if let Some(&o) = self.destnations.iter().find(|o| !inside(o)) {
+            return Err(Outside(o));
+        }
+        let units = visit;
+        let mut visit = HashSet::new();
+        for &(o, tile) in self.tiles.iter() {
+            if !inside(&o) {
+                return Err(Outside(o));
+            }
+            if !visit.insert(o) || (tile.lock().is_some() && units.contains(&o)) {
+                return Err(Overlapped(o));
+            }
+        }
+        Ok(())
+    }
+
+    /// Turn the level 90° clockwise.
+    pub fn rotate90(&self) -> Self {
+        let h = self.size.height;
+        let size = Size {
+            width: self.size.height,
+            height: self.size.width,
+        };
+        self.transform(
+            size,
+            |o| Point::new(h - 1 - o.y, o.x),
+            |m| m.clockwise(),
+            Mirror::rotate90,
+        )
+    }
+
+    /// Swap the left and the right of the level.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.size.width;
+        self.transform(
+            self.size.clone(),
+            |o| Point::new(w - 1 - o.x, o.y),
+            |m| m.flip_horizontal(),
+            Mirror::flip,
+        )
+    }
+
+    /// Swap the top and the bottom of the level.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.size.height;
+        self.transform(
+            self.size.clone(),
+            |o| Point::new(o.x, h - 1 - o.y),
+            |m| m.flip_vertical(),
+            Mirror::flip,
+        )
+    }
+
+    fn transform(
+        &self,
+        size: Size,
+        point: impl Fn(Point) -> Point,
+        movement: impl Fn(Movement) -> Movement,
+        mirror: impl Fn(Mirror) -> Mirror,
+    ) -> Self {
+        let cubes = self
+            .cubes
+            .iter()
+            .map(|cube| Cube {
+                kind: cube.kind,
+                body: cube.body.iter().cloned().map(&point).collect(),
+                command: cube.command.as_ref().map(|command| Command {
+                    is_loop: command.is_loop,
+                    movements: command
+                        .movements
+                        .iter()
+                        .map(|&(m, n)| (m.map(&movement), n))
+                        .collect(),
+                }),
+                mirror: cube.mirror.map(&mirror),
+                pursuit: cube.pursuit,
+            })
+            .collect();
+
+        Self {
+            info: self.info.clone(),
+            size,
+            cubes,
+            destnations: self.destnations.iter().cloned().map(&point).collect(),
+            tiles: self
+                .tiles
+                .iter()
+                .map(|&(o, tile)| (point(o), tile.turn(&movement)))
+                .collect(),
+            gravity: self.gravity.map(&movement),
+            falling: self.falling,
+        }
+    }
+}
+
+impl Seed {
+    /// Write the seed as a level file. It returns `None` if the seed cannot
+    /// be drawn in glyphs, e.g. a cube covers a target, a paint tile or a pit.
+    pub fn to_toml(&self) -> Option<String> {
+        use std::fmt::Write;
+
+        let mut raw = glyph::draw(
+            self.size.width,
+            self.size.height,
+            self.cubes
+                .iter()
+                .map(|cube| (cube.kind, cube.body.as_slice())),
+            &self.destnations,
+        )?;
+        for &(o, tile) in self.tiles.iter() {
+            let c = match tile {
+                Tile::Paint(kind) => glyph::paint(kind)?,
+                Tile::Pit => glyph::PIT,
+                _ => continue,
+            };
+            let cell = raw.get_mut(o.y as usize)?.get_mut(o.x as usize)?;
+            match cell {
+                ' ' => *cell = c,
+                _ => return None,
+            }
+        }
+
+        let word = |movement| match movement {
+            Movement::Left => "left",
+            Movement::Down => "down",
+            Movement::Up => "up",
+            Movement::Right => "right",
+        };
+        let color = |kind| match kind {
+            Kind::Red => Some("red"),
+            Kind::Green => Some("green"),
+            Kind::Blue => Some("blue"),
+            _ => None,
+        };
+
+        // the first unit in the drawing is where a binding points to
+        let anchor = |cube: &Cube| cube.body.iter().min_by_key(|o| (o.y, o.x)).cloned();
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let content = |text: &str| format!("content = {}\n", quote(text));
+        // tables of (name, fields after the binding, points)
+        let mut tables = Vec::<(&str, String, Vec<Point>)>::new();
+        let mut bind = |table, fields: String, point| match tables
+            .iter_mut()
+            .find(|o| o.0 == table && o.1 == fields)
+        {
+            Some(o) => o.2.push(point),
+            None => tables.push((table, fields, vec![point])),
+        };
+        for cube in self.cubes.iter() {
+            let Some(point) = anchor(cube) else {
+                continue;
+            };
+            if let Some(command) = &cube.command {
+                let mut text = String::new();
+                for &(movement, n) in command.movements.iter().filter(|o| o.1 > 0) {
+                    text.push(match movement {
+                        None => 'I',
+                        Some(Movement::Left) => 'L',
+                        Some(Movement::Down) => 'D',
+                        Some(Movement::Up) => 'U',
+                        Some(Movement::Right) => 'R',
+                    });
+                    if n > 1 {
+                        text.push_str(&n.to_string());
+                    }
+                }
+                let fields = format!(
+                    "content = {}\nlooping = {}\n",
+                    quote(&text),
+                    command.is_loop
+                );
+                bind("commands", fields, point);
+            }
+            if let Some(mirror) = cube.mirror {
+                let text = match mirror {
+                    Mirror::Opposite => "opposite",
+                    Mirror::Horizontal => "horizontal",
+                    Mirror::Vertical => "vertical",
+                    Mirror::Clockwise => "clockwise",
+                    Mirror::Anticlockwise => "anticlockwise",
+                };
+                bind("mirrors", content(text), point);
+            }
+            if let Some(pursuit) = cube.pursuit {
+                let text = match pursuit {
+                    Pursuit::Chase => "chase",
+                    Pursuit::Flee => "flee",
+                };
+                bind("pursuits", content(text), point);
+            }
+        }
+        for &(point, tile) in self.tiles.iter() {
+            match tile {
+                Tile::Conveyor(movement) => bind("conveyors", content(word(movement)), point),
+                Tile::Paint(_) | Tile::Pit => {}
+                Tile::Key(kind) => bind("keys", content(color(kind)?), point),
+                Tile::Lock(kind) => bind("locks", content(color(kind)?), point),
+                Tile::Cutter(side) => bind("cutters", content(word(side)), point),
+                Tile::Rotator { clockwise } => {
+                    let text = if clockwise {
+                        "clockwise"
+                    } else {
+                        "anticlockwise"
+                    };
+                    bind("rotators", content(text), point)
+                }
+                Tile::Blinker(Blink { period, phase }) => {
+                    let fields = format!("period = {}\nphase = {}\n", period, phase);
+                    bind("blinkers", fields, point)
+                }
+            }
+        }
+
+        let mut output = String::new();
+        let o = &mut output;
+        let _ = writeln!(o, "[map]\nraw = '''");
+        for row in raw {
+            let _ = writeln!(o, "{}", String::from_iter(row));
+        }
+        let _ = writeln!(o, "'''");
+        if let Some(gravity) = self.gravity {
+            let _ = writeln!(o, "gravity = {}", quote(word(gravity)));
+        }
+        if self.falling == Falling::Apart {
+            let _ = writeln!(o, "falling = {}", quote("apart"));
+        }
+        for (table, fields, points) in tables {
+            let binding = points
+                .iter()
+                .map(|o| format!("[{}, {}]", o.x, o.y))
+                .collect::<Vec<_>>();
+            let _ = writeln!(o, "\n[[map.{}]]", table);
+            let _ = writeln!(o, "binding = [{}]", binding.join(", "));
+            let _ = write!(o, "{}", fields);
+        }
+        let _ = writeln!(o, "\n[info]");
+        let _ = writeln!(o, "author = {}", quote(&self.info.author));
+        let _ = writeln!(o, "title = {}", quote(&self.info.title));
+        Some(output)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// internal
+
+/// The first unit not reachable from the first one through its neighbors.
+pub(crate) fn disconnected(body: &[Point]) -> Option<Point> {
+    let points = body.iter().cloned().collect::<HashSet<_>>();
+    let mut visit = HashSet::new();
+    let mut stack = body.first().into_iter().cloned().collect::<Vec<_>>();
+    while let Some(o) = stack.pop() {
+        if visit.insert(o) {
+            for movement in Movement::ALL {
+                let next = o + movement.into();
+                if points.contains(&next) {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+    body.iter().find(|o| !visit.contains(o)).cloned()
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SeedBuilder;
+
+    #[test]
+    fn transform() {
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Up), 1), (None, 2)],
+        };
+        let mut seed = SeedBuilder::new(3, 2)
+            .info("test", "test")
+            .cube(Kind::Red, [(0, 0), (1, 0)])
+            .script([(0, 0)], command)
+            .target((2, 1))
+            .tile((2, 0), Tile::Conveyor(Movement::Left))
+            .tile((0, 1), Tile::Rotator { clockwise: true })
+            .gravity(Movement::Up)
+            .build()
+            .unwrap();
+        seed.cubes[0].mirror = Some(Mirror::Clockwise);
+
+        let rotated = seed.rotate90();
+        assert_eq!(
+            rotated.size,
+            Size {
+                width: 2,
+                height: 3
+            }
+        );
+        assert_eq!(rotated.cubes[0].body, [Point::new(1, 0), Point::new(1, 1)]);
+        assert_eq!(rotated.destnations, [Point::new(0, 2)]);
+        let command = rotated.cubes[0].command.as_ref().unwrap();
+        assert_eq!(command.movements[0], (Some(Movement::Right), 1));
+        assert_eq!(rotated.gravity, Some(Movement::Right));
+        let conveyor = Tile::Conveyor(Movement::Up);
+        assert_eq!(rotated.tiles[0], (Point::new(1, 2), conveyor));
+        assert_eq!(rotated.tiles[1].1, Tile::Rotator { clockwise: true });
+        assert_eq!(rotated.rotate90().rotate90().rotate90(), seed);
+
+        let flipped = seed.flip_horizontal();
+        assert_eq!(flipped.cubes[0].body, [Point::new(2, 0), Point::new(1, 0)]);
+        assert_eq!(flipped.cubes[0].mirror, Some(Mirror::Anticlockwise));
+        assert_eq!(flipped.tiles[1].1, Tile::Rotator { clockwise: false });
+        assert_eq!(flipped.flip_horizontal(), seed);
+
+        let flipped = seed.flip_vertical();
+        assert_eq!(flipped.destnations, [Point::new(2, 0)]);
+        let command = flipped.cubes[0].command.as_ref().unwrap();
+        assert_eq!(command.movements[0], (Some(Movement::Down), 1));
+        assert_eq!(flipped.flip_vertical(), seed);
+    }
+
+    #[test]
+    fn toml() {
+        let command = Command {
+            is_loop: false,
+            movements: vec![
+                (Some(Movement::Up), 2),
+                (None, 1),
+                (Some(Movement::Left), 0),
+            ],
+        };
+        let blink = Blink {
+            period: 3,
+            phase: 1,
+        };
+        let mut seed = SeedBuilder::new(3, 2)
+            .info("say \"hi\"", "w")
+            .cube(Kind::Red, [(1, 0), (0, 1), (1, 1)])
+            .cube(Kind::Blue, [(2, 1)])
+            .target((0, 0))
+            .tile((1, 0), Tile::Conveyor(Movement::Down))
+            .tile((0, 0), Tile::Conveyor(Movement::Down))
+            .tile((2, 0), Tile::Paint(Kind::Green))
+            .tile((1, 1), Tile::Key(Kind::Red))
+            .tile((2, 1), Tile::Blinker(blink))
+            .gravity(Movement::Down)
+            .falling(Falling::Apart)
+            .build()
+            .unwrap();
+        seed.cubes[1].mirror = Some(Mirror::Opposite);
+
+        // builders reject zero steps, which are left out in level files.
+        for cube in seed.cubes.iter_mut() {
+            cube.command = Some(command.clone());
+        }
+
+        let expected = r#"[map]
+raw = '''
+xR&
+R/B
+'''
+gravity = "down"
+falling = "apart"
+
+[[map.commands]]
+binding = [[1, 0], [2, 1]]
+content = "U2I"
+looping = false
+
+[[map.mirrors]]
+binding = [[2, 1]]
+content = "opposite"
+
+[[map.conveyors]]
+binding = [[1, 0], [0, 0]]
+content = "down"
+
+[[map.keys]]
+binding = [[1, 1]]
+content = "red"
+
+[[map.blinkers]]
+binding = [[2, 1]]
+period = 3
+phase = 1
+
+[info]
+author = "w"
+title = "say \"hi\""
+"#;
+        assert_eq!(seed.to_toml().as_deref(), Some(expected));
+
+        let covered = Seed {
+            destnations: vec![Point::new(1, 0)],
+            ..seed.clone()
+        };
+        assert_eq!(covered.to_toml(), None);
+        let mut covered = seed.clone();
+        covered
+            .tiles
+            .push((Point::new(1, 1), Tile::Paint(Kind::Red)));
+        assert_eq!(covered.to_toml(), None);
+        let mut covered = seed;
+        covered.tiles.push((Point::new(2, 1), Tile::Pit));
+        assert_eq!(covered.to_toml(), None);
+    }
+}
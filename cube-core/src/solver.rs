@@ -0,0 +1,60 @@
+use super::{cube::Movement, explore::StateGraph, seed::Seed, state::CubeCore};
+
+/// All inputs that a player is able to give in one round.
+pub const INPUTS: [Option<Movement>; 5] = [
+    None,
+    Some(Movement::Left),
+    Some(Movement::Down),
+    Some(Movement::Up),
+    Some(Movement::Right),
+];
+
+/// Find one of the shortest input sequences that covers all destinations by
+/// searching every state reachable through `CubeCore::commit`. It gives up
+/// and returns `None` if no solution is found in `limit` states.
+pub fn solve(seed: &Seed, limit: usize) -> Option<Vec<Option<Movement>>> {
+    let graph = StateGraph::search(seed, limit, true)?;
+    let path = graph.path()?;
+    Some(path.into_iter().skip(1).map(|(_, input)| input).collect())
+}
+
+pub(crate) fn solved(state: &CubeCore) -> bool {
+    state.goals().all(|(_, covered)| covered)
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SeedBuilder;
+    use crate::cube::Kind;
+
+    #[test]
+    fn shortest() {
+        /******
+         *G W *
+         *  W *
+         *   x*
+         ******/
+
+        let seed = SeedBuilder::new(4, 3)
+            .cube(Kind::Green, [(0, 0)])
+            .cube(Kind::White, [(2, 0), (2, 1)])
+            .target((3, 2))
+            .build()
+            .unwrap();
+
+        let solution = solve(&seed, 1000).unwrap();
+        assert_eq!(solution.len(), 5);
+        assert_eq!(solution.first(), Some(&Some(Movement::Down)));
+
+        let mut game = CubeCore::new(&seed);
+        for input in solution {
+            game.commit(input).for_each(drop);
+        }
+        assert!(solved(&game));
+        assert_eq!(solve(&seed, 4), None);
+    }
+}
@@ -1,45 +1,69 @@
 use super::{
-    cube::{Kind, Motion, Movement, Point},
-    rule::{Collection, Diff, Snapshot, Unit},
-    seed::{Cube, Seed},
+    board::Board,
+    cube::{Kind, Motion, Movement, Point, Tile},
+    rule::{Collection, Diff, Explanation, Snapshot, StateKey, Trace, Unit},
+    seed::{Cube, Seed, SeedError},
 };
 
+#[derive(Clone)]
 pub struct CubeCore {
     dest: Vec<Point>,
+    tile: Vec<(Point, Tile)>,
     last: Option<(Collection, Snapshot)>,
+    input: Option<Movement>, // movement of the last round
     base: (Collection, Snapshot),
 }
 
 impl CubeCore {
     pub fn new(seed: &Seed) -> Self {
         fn convert(cube: &Cube) -> (Kind, &[Point], Motion) {
+            let script = cube.command.as_ref().map(|command| {
+                Motion::from_sequence(command.is_loop, command.movements.iter().cloned())
+            });
+            let mirror = cube.mirror.map(Motion::from_mirror);
+            let pursuit = cube.pursuit.map(Motion::from_pursuit);
+            let mut motions = script.into_iter().chain(mirror).chain(pursuit);
             (
                 cube.kind,
                 cube.body.as_slice(),
-                match &cube.command {
-                    None => Motion::new(),
-                    Some(command) => {
-                        Motion::from_sequence(command.is_loop, command.movements.iter().cloned())
+                match (motions.next(), motions.next()) {
+                    (None, _) => Motion::new(),
+                    (Some(motion), None) => motion,
+                    (Some(l), Some(r)) => {
+                        let motions = [l, r].into_iter().chain(motions);
+                        Motion::from_iter(motions.map(|motion| (motion, Vec::new())))
                     }
                 },
             )
         }
 
         let dest = seed.destnations.clone();
+        let tile = seed.tiles.clone();
         let collection = Collection::new(
             seed.size.width.max(1) as usize,
             seed.size.height.max(1) as usize,
+            seed.gravity,
+            seed.falling,
+            seed.tiles.iter().cloned(),
             seed.cubes.iter().map(convert),
         );
         let snapshot = collection.snapshot();
 
         Self {
             dest,
+            tile,
             last: None,
+            input: None,
             base: (collection, snapshot),
         }
     }
 
+    /// Same as `new`, but it rejects seeds which would not be played as
+    /// they look, see `Seed::check`.
+    pub fn try_new(seed: &Seed) -> Result<Self, SeedError> {
+        seed.check().map(|_| Self::new(seed))
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = Unit> + '_ {
         self.base.1.iter()
     }
@@ -48,27 +72,41 @@ impl CubeCore {
         self.dest.iter().map(|&o| (o, self.base.1.contains(o)))
     }
 
+    /// Tiles which are still there, without picked keys or opened locks.
+    pub fn tiles(&self) -> impl Iterator<Item = (Point, Tile)> + '_ {
+        let base = &self.base.0;
+        self.tile.iter().cloned().filter(|(o, _)| !base.spent(*o))
+    }
+
     pub fn commit(&mut self, movement: Option<Movement>) -> impl Iterator<Item = Diff> + '_ {
         let mut base = self.base.0.clone();
-        base.commit(movement);
+        base.commit(movement, None);
         let snapshot = base.snapshot();
         let mut base = (base, snapshot);
 
         std::mem::swap(&mut self.base, &mut base);
+        self.input = movement;
         let last = self.last.insert(base);
         last.1.differ(&self.base.1)
     }
 
+    pub fn preview(&self, movement: Option<Movement>) -> Vec<Diff> {
+        let mut base = self.base.0.clone();
+        base.commit(movement, None);
+        self.base.1.differ(&base.snapshot()).collect()
+    }
+
     pub fn remake(&mut self, movement: Option<Movement>) -> impl Iterator<Item = Diff> + '_ {
         let pair = match &mut self.last {
             None => (&self.base.1, &self.base.1),
             Some(last) => {
                 let mut base = last.0.clone();
-                base.commit(movement);
+                base.commit(movement, None);
 
                 last.1 = base.snapshot();
                 std::mem::swap(&mut self.base.1, &mut last.1);
                 self.base.0 = base;
+                self.input = movement;
 
                 (&last.1, &self.base.1)
             }
@@ -77,6 +115,25 @@ impl CubeCore {
         pair.0.differ(&pair.1)
     }
 
+    /// Explain why the unit is constrained in the last round. The round is
+    /// replayed with tracing, so normal rounds cost nothing extra.
+    pub fn explain(&self, id: usize) -> Option<Explanation> {
+        let last = self.last.as_ref()?;
+        let mut trace = Trace::new();
+        let mut base = last.0.clone();
+        base.commit(self.input, Some(&mut trace));
+        trace.get(id).cloned()
+    }
+
+    pub(crate) fn key(&self) -> StateKey {
+        self.base.0.key()
+    }
+
+    /// Frozen cells of (index, kind, position), see `StateKey::broken`.
+    pub(crate) fn frozen(&self) -> impl Iterator<Item = (usize, Kind, Point)> + '_ {
+        self.base.0.frozen()
+    }
+
     pub fn width(&self) -> usize {
         self.base.0.width()
     }
@@ -85,3 +142,10 @@ impl CubeCore {
         self.base.0.height()
     }
 }
+
+/// Draw the current board, see `Board`.
+impl std::fmt::Display for CubeCore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Board::from(self).fmt(f)
+    }
+}
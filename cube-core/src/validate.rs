@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{
+    cube::{Kind, Movement, Point},
+    seed::{Cube, Seed},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LevelWarning {
+    /// the destination is always covered by a wall, a brick or a lock
+    FrozenDestination(Point),
+    /// the destination is outside of the board
+    OutsideDestination(Point),
+    /// the unit of a cube is outside of the board
+    OutsideCube(Point),
+    /// there is no green cube to control
+    NoGreenCube,
+    /// a command is bound to the white cube at the point
+    CommandOnWhite(Point),
+    /// destinations cannot be all covered by movable units
+    NotEnoughUnits { units: usize, destinations: usize },
+    /// more than one cube is placed at the point
+    Overlapped(Point),
+    /// no movable unit is able to reach the destination
+    Unreachable(Point),
+}
+
+impl std::fmt::Display for LevelWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use LevelWarning::*;
+        match self {
+            FrozenDestination(o) => write!(f, "destination ({}, {}) is under a wall", o.x, o.y),
+            OutsideDestination(o) => write!(f, "destination ({}, {}) is outside", o.x, o.y),
+            OutsideCube(o) => write!(f, "cube at ({}, {}) is outside", o.x, o.y),
+            NoGreenCube => write!(f, "no green cube"),
+            CommandOnWhite(o) => write!(f, "command is bound to white cube ({}, {})", o.x, o.y),
+            NotEnoughUnits {
+                units,
+                destinations,
+            } => write!(
+                f,
+                "{} movable units for {} destinations",
+                units, destinations
+            ),
+            Overlapped(o) => write!(f, "cubes overlap at ({}, {})", o.x, o.y),
+            Unreachable(o) => write!(f, "destination ({}, {}) is unreachable", o.x, o.y),
+        }
+    }
+}
+
+/// Find design mistakes of a level. It never runs the level, so a level
+/// without warnings is not necessarily solvable.
+pub fn validate(seed: &Seed) -> Vec<LevelWarning> {
+    use LevelWarning::*;
+
+    let inside =
+        |o: &Point| 0 <= o.x && o.x < seed.size.width && 0 <= o.y && o.y < seed.size.height;
+    let frozen = |cube: &Cube| match cube.kind {
+        Kind::White => cube.command.is_none() && cube.mirror.is_none() && cube.pursuit.is_none(),
+        Kind::Brick => true,
+        _ => false,
+    };
+
+    // bricks are walls without bombs, and so are locks without their keys.
+    let mut output = Vec::new();
+    let bomb = seed.cubes.iter().any(|cube| cube.kind == Kind::Bomb);
+    let keys = seed
+        .tiles
+        .iter()
+        .filter_map(|(_, tile)| tile.key())
+        .collect::<HashSet<_>>();
+    let locks = seed
+        .tiles
+        .iter()
+        .filter(|(_, tile)| tile.lock().is_some_and(|kind| !keys.contains(&kind)))
+        .map(|&(o, _)| o);
+    let walls = seed
+        .cubes
+        .iter()
+        .filter(|cube| frozen(cube) && (cube.kind != Kind::Brick || !bomb))
+        .flat_map(|cube| cube.body.iter().cloned())
+        .chain(locks)
+        .collect::<HashSet<_>>();
+    let pits = seed
+        .tiles
+        .iter()
+        .filter(|(_, tile)| tile.pit())
+        .map(|&(o, _)| o)
+        .collect::<HashSet<_>>();
+
+    // cubes
+    if !seed.cubes.iter().any(|cube| cube.kind == Kind::Green) {
+        output.push(NoGreenCube);
+    }
+    for cube in seed.cubes.iter() {
+        if let (Kind::White, Some(_), Some(&o)) = (cube.kind, &cube.command, cube.body.first()) {
+            output.push(CommandOnWhite(o));
+        }
+    }
+    let mut count = HashMap::with_capacity(seed.cubes.len());
+    for &o in seed.cubes.iter().flat_map(|cube| cube.body.iter()) {
+        let n = count.entry(o).or_insert(0);
+        *n += 1;
+        match *n {
+            1 if !inside(&o) => output.push(OutsideCube(o)),
+            2 => output.push(Overlapped(o)),
+            _ => {}
+        }
+    }
+
+    // destinations
+    let mut open = Vec::with_capacity(seed.destnations.len());
+    for &o in seed.destnations.iter() {
+        if !inside(&o) {
+            output.push(OutsideDestination(o));
+        } else if walls.contains(&o) {
+            output.push(FrozenDestination(o));
+        } else {
+            open.push(o);
+        }
+    }
+
+    let units = seed
+        .cubes
+        .iter()
+        .filter(|cube| !frozen(cube))
+        .map(|cube| cube.body.len())
+        .sum::<usize>();
+    if units < open.len() {
+        let destinations = open.len();
+        output.push(NotEnoughUnits {
+            units,
+            destinations,
+        });
+    }
+
+    // flood fill from movable units, ignoring the shapes of cubes, and
+    // units moving onto pits are gone.
+    let mut visit = HashSet::new();
+    let mut queue = seed
+        .cubes
+        .iter()
+        .filter(|cube| !frozen(cube))
+        .flat_map(|cube| cube.body.iter().cloned())
+        .filter(|o| inside(o) && visit.insert(*o))
+        .collect::<VecDeque<_>>();
+    while let Some(o) = queue.pop_front() {
+        for movement in Movement::ALL {
+            let next = o + movement.into();
+            let open = !walls.contains(&next) && !pits.contains(&next);
+            if inside(&next) && open && visit.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    for &o in open.iter().filter(|o| !visit.contains(o)) {
+        output.push(Unreachable(o));
+    }
+
+    output
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SeedBuilder;
+    use crate::seed::Command;
+
+    #[test]
+    fn warnings() {
+        /*******
+         *R W x*
+         *B WWW*
+         *  W x*
+         *W   x*
+         *******/
+
+        let command = Command {
+            is_loop: true,
+            movements: vec![(Some(Movement::Right), 1)],
+        };
+        let mut seed = SeedBuilder::new(5, 4)
+            .cube(Kind::Red, [(0, 0)])
+            .cube(Kind::Blue, [(0, 1)])
+            .cube(Kind::White, [(2, 0), (2, 1), (3, 1), (4, 1), (2, 2)])
+            .cube(Kind::White, [(0, 3)])
+            .script([(0, 3)], command)
+            .target((4, 0))
+            .target((4, 2))
+            .target((4, 3))
+            .target((3, 1))
+            .build()
+            .unwrap();
+
+        // builders reject overlaps and points outside, so they come later.
+        seed.cubes[1].body.push(Point::new(0, 0));
+        seed.destnations.push(Point::new(5, 3));
+
+        use LevelWarning::*;
+        let expected = vec![
+            NoGreenCube,
+            CommandOnWhite(Point::new(0, 3)),
+            Overlapped(Point::new(0, 0)),
+            FrozenDestination(Point::new(3, 1)),
+            OutsideDestination(Point::new(5, 3)),
+            Unreachable(Point::new(4, 0)),
+        ];
+        assert_eq!(validate(&seed), expected);
+
+        seed.cubes.truncate(1);
+        seed.cubes[0].kind = Kind::Green;
+        seed.cubes[0].body.push(Point::new(-1, 0));
+        let expected = vec![
+            OutsideCube(Point::new(-1, 0)),
+            OutsideDestination(Point::new(5, 3)),
+            NotEnoughUnits {
+                units: 2,
+                destinations: 4,
+            },
+        ];
+        assert_eq!(validate(&seed), expected);
+    }
+
+    #[test]
+    fn obstacles() {
+        use crate::cube::Tile;
+        use LevelWarning::*;
+
+        /******
+         * GKKx*
+         ******/
+
+        // bricks are walls without bombs, and never movable units.
+        let level = SeedBuilder::new(5, 1)
+            .cube(Kind::Green, [(1, 0)])
+            .cube(Kind::Brick, [(2, 0), (3, 0)])
+            .target((3, 0))
+            .target((4, 0));
+        let expected = vec![
+            FrozenDestination(Point::new(3, 0)),
+            Unreachable(Point::new(4, 0)),
+        ];
+        assert_eq!(validate(&level.clone().build().unwrap()), expected);
+        let seed = level.cube(Kind::Bomb, [(0, 0)]).build().unwrap();
+        assert_eq!(validate(&seed), vec![]);
+
+        /*****
+         *GL x*
+         *****/
+
+        // locks are walls without their keys.
+        let level = SeedBuilder::new(4, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .target((3, 0))
+            .tile((1, 0), Tile::Lock(Kind::Red));
+        let seed = level.clone().build().unwrap();
+        assert_eq!(validate(&seed), vec![Unreachable(Point::new(3, 0))]);
+        let seed = level.tile((2, 0), Tile::Key(Kind::Red)).build().unwrap();
+        assert_eq!(validate(&seed), vec![]);
+
+        /****
+         *G.x*
+         ****/
+
+        // units moving onto pits are gone.
+        let seed = SeedBuilder::new(3, 1)
+            .cube(Kind::Green, [(0, 0)])
+            .tile((1, 0), Tile::Pit)
+            .target((2, 0))
+            .build()
+            .unwrap();
+        assert_eq!(validate(&seed), vec![Unreachable(Point::new(2, 0))]);
+    }
+}